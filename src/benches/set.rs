@@ -7,7 +7,8 @@ extern crate rand_xorshift;
 extern crate test;
 use self::rand::{Rng, SeedableRng};
 use self::rand_xorshift::XorShiftRng;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::hash::Hash;
 
 fn random(n1: usize, n2: usize) -> [BTreeSet<usize>; 2] {
     let mut rng = XorShiftRng::from_seed([68; 16]);
@@ -63,6 +64,11 @@ fn superset(factor: usize, n2: usize) -> [BTreeSet<u32>; 2] {
     sets
 }
 
+fn swapped<T>(mut sets: [BTreeSet<T>; 2]) -> [BTreeSet<T>; 2] {
+    sets.swap(0, 1);
+    sets
+}
+
 fn stagger(n1: usize, factor: usize) -> [BTreeSet<u32>; 2] {
     use std::cmp::min;
     let n2 = n1 * factor;
@@ -76,6 +82,210 @@ fn stagger(n1: usize, factor: usize) -> [BTreeSet<u32>; 2] {
     sets
 }
 
+// Both sets take turns owning runs of `cluster` consecutive elements.
+fn clustered(n: usize, cluster: usize) -> [BTreeSet<u32>; 2] {
+    let mut sets = [BTreeSet::new(), BTreeSet::new()];
+    for elt in 0..(n * 2) {
+        let i = elt / cluster % 2;
+        sets[i].insert(elt as u32);
+    }
+    assert_eq!(sets[0].len(), n);
+    assert_eq!(sets[1].len(), n);
+    sets
+}
+
+// Each set is dense on one side of zero and sparse on the other,
+// so the sizes remaining to be visited change drastically halfway.
+fn lopsided(n: usize, factor: usize) -> [BTreeSet<i32>; 2] {
+    let mut sets = [neg(n), pos(n)];
+    for i in 1..=(n / factor) as i32 {
+        sets[0].insert(i * factor as i32);
+        sets[1].insert(-i * factor as i32);
+    }
+    assert_eq!(sets[0].len(), n + n / factor);
+    assert_eq!(sets[1].len(), n + n / factor);
+    sets
+}
+
+// Owned results, for comparison with in-place operations,
+// which need to start from their own copy of the first set.
+fn clone_first<T: Clone>(a: &BTreeSet<T>, _: &BTreeSet<T>) -> BTreeSet<T> {
+    a.clone()
+}
+
+fn intersection_bitand<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> BTreeSet<T> {
+    a & b
+}
+
+fn intersection_collect<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> BTreeSet<T> {
+    rust_bench_btreeset::set_new::intersection(a, b)
+        .cloned()
+        .collect()
+}
+
+fn intersect_with<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> BTreeSet<T> {
+    let mut a = a.clone();
+    rust_bench_btreeset::set_new::intersect_with(&mut a, b);
+    a
+}
+
+fn clone_both<T: Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> [BTreeSet<T>; 2] {
+    [a.clone(), b.clone()]
+}
+
+fn difference_sub<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> BTreeSet<T> {
+    a - b
+}
+
+fn difference_collect<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> BTreeSet<T> {
+    rust_bench_btreeset::set_new::difference(a, b)
+        .cloned()
+        .collect()
+}
+
+fn difference_with<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> BTreeSet<T> {
+    let mut a = a.clone();
+    rust_bench_btreeset::set_new::difference_with(&mut a, b);
+    a
+}
+
+fn symmetric_difference_bitxor<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> BTreeSet<T> {
+    a ^ b
+}
+
+fn union_bitor<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> BTreeSet<T> {
+    a | b
+}
+
+fn union_collect<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> BTreeSet<T> {
+    rust_bench_btreeset::set_new::union(a, b).cloned().collect()
+}
+
+fn union_with<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> BTreeSet<T> {
+    let mut a = a.clone();
+    rust_bench_btreeset::set_new::union_with(&mut a, b.clone());
+    a
+}
+
+// Maps keyed like the sets, for the joins.
+fn maps<T: Ord + Clone>(sets: [BTreeSet<T>; 2]) -> [BTreeMap<T, T>; 2] {
+    let [a, b] = sets;
+    let map = |set: BTreeSet<T>| set.into_iter().map(|elt| (elt.clone(), elt)).collect();
+    [map(a), map(b)]
+}
+
+// Sorted vectors holding the same elements as the sets.
+fn vecs<T: Ord>(sets: [BTreeSet<T>; 2]) -> [Vec<T>; 2] {
+    let [a, b] = sets;
+    [a.into_iter().collect(), b.into_iter().collect()]
+}
+
+// Hash sets holding the same elements as the sets, as a baseline that
+// produces the same count of elements, in arbitrary order.
+fn hash_sets<T: Eq + Hash>(sets: [BTreeSet<T>; 2]) -> [HashSet<T>; 2] {
+    let [a, b] = sets;
+    [a.into_iter().collect(), b.into_iter().collect()]
+}
+
+fn inner_join_get<'a, K: Ord, V>(
+    a: &'a BTreeMap<K, V>,
+    b: &'a BTreeMap<K, V>,
+) -> impl Iterator<Item = (&'a K, &'a V, &'a V)> {
+    a.iter()
+        .filter_map(move |(k, a_value)| b.get(k).map(|b_value| (k, a_value, b_value)))
+}
+
+// Classification in three passes, as it would be done without classify.
+fn classify_separately<'a, T: Ord>(
+    a: &'a BTreeSet<T>,
+    b: &'a BTreeSet<T>,
+) -> impl Iterator<Item = &'a T> {
+    use rust_bench_btreeset::set_peeking::{difference, intersection};
+    difference(a, b)
+        .chain(intersection(a, b))
+        .chain(difference(b, a))
+}
+
+const PAGE_LEN: usize = 100;
+const PAGES: usize = 200;
+
+// Pages through an operation, resuming each page after the last element
+// of the previous one, and counts the elements of all pages.
+fn paged<'a, T: 'a, I, J, F>(first: I, resume: F) -> usize
+where
+    I: Iterator<Item = &'a T>,
+    J: Iterator<Item = &'a T>,
+    F: Fn(&'a T) -> J,
+{
+    use rust_bench_btreeset::set_peeking::page;
+    let (items, mut cursor) = page(first, PAGE_LEN);
+    let mut count = items.len();
+    for _ in 1..PAGES {
+        match cursor {
+            Some(last_seen) => {
+                let (items, next) = page(resume(last_seen), PAGE_LEN);
+                count += items.len();
+                cursor = next;
+            }
+            None => break,
+        }
+    }
+    count
+}
+
+fn difference_paged<T: Ord>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> usize {
+    use rust_bench_btreeset::set_peeking::{difference, difference_after};
+    paged(difference(a, b), |last| difference_after(a, b, last))
+}
+
+fn intersection_paged<T: Ord>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> usize {
+    use rust_bench_btreeset::set_peeking::{intersection, intersection_after};
+    paged(intersection(a, b), |last| intersection_after(a, b, last))
+}
+
+fn union_paged<T: Ord>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> usize {
+    use rust_bench_btreeset::set_peeking::{union, union_after};
+    paged(union(a, b), |last| union_after(a, b, last))
+}
+
+// The same number of elements, in a single walk.
+fn difference_walked<T: Ord>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> usize {
+    rust_bench_btreeset::set_peeking::difference(a, b)
+        .take(PAGES * PAGE_LEN)
+        .count()
+}
+
+fn intersection_walked<T: Ord>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> usize {
+    rust_bench_btreeset::set_peeking::intersection(a, b)
+        .take(PAGES * PAGE_LEN)
+        .count()
+}
+
+fn union_walked<T: Ord>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> usize {
+    rust_bench_btreeset::set_peeking::union(a, b)
+        .take(PAGES * PAGE_LEN)
+        .count()
+}
+
+// Consumers for comparing the iterators' own fold, which count uses,
+// with stepping through them, and for comparing their own last
+// with the default one through fold.
+trait Consume: Iterator + Sized {
+    fn count_by_next(mut self) -> usize {
+        let mut count = 0;
+        while self.next().is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    fn last_by_fold(self) -> Option<Self::Item> {
+        self.fold(None, |_, next| Some(next))
+    }
+}
+
+impl<I: Iterator> Consume for I {}
+
 macro_rules! set_bench {
     ($bench_name: ident, $sets: expr, $oper_name: path, $consume_name: ident) => {
         #[bench]
@@ -90,6 +300,8 @@ macro_rules! set_bench {
 macro_rules! set_benches {
     ($mod_name: ident, $oper_name: path, $consume_name: ident, $($bench_name: ident: $sets: expr;)+) => {
         mod $mod_name {
+            #[allow(unused_imports)]
+            use super::Consume;
             $(
                 set_bench!($bench_name, $sets, $oper_name, $consume_name);
             )+
@@ -99,79 +311,90 @@ macro_rules! set_benches {
 
 macro_rules! vanilla_benches {
     ($mod_name: ident, $oper_name: path, $consume_name: ident) => {
+        vanilla_benches!($mod_name, $oper_name, $consume_name, std::convert::identity);
+    };
+    ($mod_name: ident, $oper_name: path, $consume_name: ident, $prepare: path) => {
         set_benches!($mod_name, $oper_name, $consume_name,
-            equal_100_vs_100:           super::subset(100, 1);
-            random_100_vs_100:          super::random(100, 100);
-            random_100_vs_10k:          super::random(100, 10_000);
-            random_100_vs_1600:         super::random(100, 1_600);
-            random_10k_vs_10k:          super::random(10_000, 10_000);
-            stagger_100_vs_100:         super::stagger(100, 1);
-            stagger_100_vs_10k:         super::stagger(100, 100);
-            split_100_neg_vs_100_pos:   [super::neg(100), super::pos(100)];
-            split_100_neg_vs_10k_pos:   [super::neg(100), super::pos(10_000)];
-            split_100_pos_vs_100_neg:   [super::pos(100), super::neg(100)];
-            split_100_pos_vs_10k_neg:   [super::pos(100), super::neg(10_000)];
-            split_10k_neg_vs_100_pos:   [super::neg(10_000), super::pos(100)];
-            split_10k_neg_vs_10k_pos:   [super::neg(10_000), super::pos(10_000)];
-            split_10k_pos_vs_100_neg:   [super::pos(10_000), super::neg(100)];
-            split_10k_pos_vs_10k_neg:   [super::pos(10_000), super::neg(10_000)];
-            subset_010_vs_100:          super::subset(10, 10);
-            subset_100_vs_10k:          super::subset(100, 100);
-            superset_100_vs_010:        super::superset(10, 10);
-            superset_10k_vs_100:        super::superset(100, 100);
+            clustered_10k_by_10:        $prepare(super::clustered(10_000, 10));
+            clustered_10k_by_100:       $prepare(super::clustered(10_000, 100));
+            equal_100_vs_100:           $prepare(super::subset(100, 1));
+            random_100_vs_100:          $prepare(super::random(100, 100));
+            random_100_vs_10k:          $prepare(super::random(100, 10_000));
+            random_100_vs_1600:         $prepare(super::random(100, 1_600));
+            random_10_vs_10k:           $prepare(super::random(10, 10_000));
+            random_10k_vs_10k:          $prepare(super::random(10_000, 10_000));
+            stagger_100_vs_100:         $prepare(super::stagger(100, 1));
+            stagger_100_vs_10k:         $prepare(super::stagger(100, 100));
+            lopsided_10k_vs_10k:        $prepare(super::lopsided(10_000, 100));
+            split_100_neg_vs_100_pos:   $prepare([super::neg(100), super::pos(100)]);
+            split_100_neg_vs_10k_pos:   $prepare([super::neg(100), super::pos(10_000)]);
+            split_100_pos_vs_100_neg:   $prepare([super::pos(100), super::neg(100)]);
+            split_100_pos_vs_10k_neg:   $prepare([super::pos(100), super::neg(10_000)]);
+            split_10k_neg_vs_100_pos:   $prepare([super::neg(10_000), super::pos(100)]);
+            split_10k_neg_vs_10k_pos:   $prepare([super::neg(10_000), super::pos(10_000)]);
+            split_10k_pos_vs_100_neg:   $prepare([super::pos(10_000), super::neg(100)]);
+            split_10k_pos_vs_10k_neg:   $prepare([super::pos(10_000), super::neg(10_000)]);
+            subset_010_vs_100:          $prepare(super::subset(10, 10));
+            subset_100_vs_10k:          $prepare(super::subset(100, 100));
+            superset_100_vs_010:        $prepare(super::superset(10, 10));
+            superset_10k_vs_010:        $prepare(super::superset(1_000, 10));
+            superset_10k_vs_100:        $prepare(super::superset(100, 100));
         );
     }
 }
 
 macro_rules! stagger_benches {
     ($mod_name: ident, $oper_name: path, $consume_name: ident) => {
+        stagger_benches!($mod_name, $oper_name, $consume_name, std::convert::identity);
+    };
+    ($mod_name: ident, $oper_name: path, $consume_name: ident, $prepare: path) => {
         #[cfg(feature = "stagger")]
         set_benches!($mod_name, $oper_name, $consume_name,
-            _000_001_vs_x01:    super::stagger(1, 1);
-            _000_002_vs_x01:    super::stagger(2, 1);
-            _000_004_vs_x01:    super::stagger(4, 1);
-            _000_006_vs_x01:    super::stagger(6, 1);
-            _000_008_vs_x01:    super::stagger(8, 1);
-            _000_010_vs_x02:    super::stagger(10, 2);
-            _000_010_vs_x03:    super::stagger(10, 3);
-            _000_010_vs_x04:    super::stagger(10, 4);
-            _000_010_vs_x05:    super::stagger(10, 5);
-            _000_010_vs_x15:    super::stagger(10, 15);
-            _000_010_vs_x16:    super::stagger(10, 16);
-            _000_100_vs_x04:    super::stagger(100, 4);
-            _000_100_vs_x05:    super::stagger(100, 5);
-            _000_100_vs_x06:    super::stagger(100, 6);
-            _000_100_vs_x07:    super::stagger(100, 7);
-            _000_100_vs_x15:    super::stagger(100, 15);
-            _000_100_vs_x16:    super::stagger(100, 16);
-            _000_200_vs_x05:    super::stagger(200, 5);
-            _000_200_vs_x06:    super::stagger(200, 6);
-            _000_200_vs_x07:    super::stagger(200, 7);
-            _000_200_vs_x08:    super::stagger(200, 8);
-            _000_200_vs_x15:    super::stagger(200, 15);
-            _000_200_vs_x16:    super::stagger(200, 16);
-            _000_500_vs_x12:    super::stagger(500, 12);
-            _000_500_vs_x13:    super::stagger(500, 13);
-            _000_500_vs_x14:    super::stagger(500, 14);
-            _000_500_vs_x15:    super::stagger(500, 15);
-            _000_500_vs_x16:    super::stagger(500, 16);
-            _001_000_vs_x15:    super::stagger(1_000, 15);
-            _001_000_vs_x16:    super::stagger(1_000, 16);
-            _001_000_vs_x17:    super::stagger(1_000, 17);
-            _001_000_vs_x18:    super::stagger(1_000, 18);
-            _001_000_vs_x19:    super::stagger(1_000, 19);
-            _010_000_vs_x15:    super::stagger(10_000, 15);
-            _010_000_vs_x16:    super::stagger(10_000, 16);
-            _010_000_vs_x17:    super::stagger(10_000, 17);
-            _010_000_vs_x18:    super::stagger(10_000, 18);
-            _010_000_vs_x19:    super::stagger(10_000, 19);
-            _010_000_vs_x20:    super::stagger(10_000, 20);
-            _100_000_vs_x15:    super::stagger(100_000, 15);
-            _100_000_vs_x16:    super::stagger(100_000, 16);
-            _100_000_vs_x17:    super::stagger(100_000, 17);
-            _100_000_vs_x18:    super::stagger(100_000, 18);
-            _100_000_vs_x19:    super::stagger(100_000, 19);
-            _100_000_vs_x20:    super::stagger(100_000, 20);
+            _000_001_vs_x01:    $prepare(super::stagger(1, 1));
+            _000_002_vs_x01:    $prepare(super::stagger(2, 1));
+            _000_004_vs_x01:    $prepare(super::stagger(4, 1));
+            _000_006_vs_x01:    $prepare(super::stagger(6, 1));
+            _000_008_vs_x01:    $prepare(super::stagger(8, 1));
+            _000_010_vs_x02:    $prepare(super::stagger(10, 2));
+            _000_010_vs_x03:    $prepare(super::stagger(10, 3));
+            _000_010_vs_x04:    $prepare(super::stagger(10, 4));
+            _000_010_vs_x05:    $prepare(super::stagger(10, 5));
+            _000_010_vs_x15:    $prepare(super::stagger(10, 15));
+            _000_010_vs_x16:    $prepare(super::stagger(10, 16));
+            _000_100_vs_x04:    $prepare(super::stagger(100, 4));
+            _000_100_vs_x05:    $prepare(super::stagger(100, 5));
+            _000_100_vs_x06:    $prepare(super::stagger(100, 6));
+            _000_100_vs_x07:    $prepare(super::stagger(100, 7));
+            _000_100_vs_x15:    $prepare(super::stagger(100, 15));
+            _000_100_vs_x16:    $prepare(super::stagger(100, 16));
+            _000_200_vs_x05:    $prepare(super::stagger(200, 5));
+            _000_200_vs_x06:    $prepare(super::stagger(200, 6));
+            _000_200_vs_x07:    $prepare(super::stagger(200, 7));
+            _000_200_vs_x08:    $prepare(super::stagger(200, 8));
+            _000_200_vs_x15:    $prepare(super::stagger(200, 15));
+            _000_200_vs_x16:    $prepare(super::stagger(200, 16));
+            _000_500_vs_x12:    $prepare(super::stagger(500, 12));
+            _000_500_vs_x13:    $prepare(super::stagger(500, 13));
+            _000_500_vs_x14:    $prepare(super::stagger(500, 14));
+            _000_500_vs_x15:    $prepare(super::stagger(500, 15));
+            _000_500_vs_x16:    $prepare(super::stagger(500, 16));
+            _001_000_vs_x15:    $prepare(super::stagger(1_000, 15));
+            _001_000_vs_x16:    $prepare(super::stagger(1_000, 16));
+            _001_000_vs_x17:    $prepare(super::stagger(1_000, 17));
+            _001_000_vs_x18:    $prepare(super::stagger(1_000, 18));
+            _001_000_vs_x19:    $prepare(super::stagger(1_000, 19));
+            _010_000_vs_x15:    $prepare(super::stagger(10_000, 15));
+            _010_000_vs_x16:    $prepare(super::stagger(10_000, 16));
+            _010_000_vs_x17:    $prepare(super::stagger(10_000, 17));
+            _010_000_vs_x18:    $prepare(super::stagger(10_000, 18));
+            _010_000_vs_x19:    $prepare(super::stagger(10_000, 19));
+            _010_000_vs_x20:    $prepare(super::stagger(10_000, 20));
+            _100_000_vs_x15:    $prepare(super::stagger(100_000, 15));
+            _100_000_vs_x16:    $prepare(super::stagger(100_000, 16));
+            _100_000_vs_x17:    $prepare(super::stagger(100_000, 17));
+            _100_000_vs_x18:    $prepare(super::stagger(100_000, 18));
+            _100_000_vs_x19:    $prepare(super::stagger(100_000, 19));
+            _100_000_vs_x20:    $prepare(super::stagger(100_000, 20));
         );
     }
 }
@@ -179,20 +402,145 @@ macro_rules! stagger_benches {
 vanilla_benches! {dif_old, rust_bench_btreeset::set_now::difference, count}
 vanilla_benches! {dif_new, rust_bench_btreeset::set_new::difference, count}
 vanilla_benches! {dif_peeking, rust_bench_btreeset::set_peeking::difference, count}
+vanilla_benches! {dif_switch, rust_bench_btreeset::set_switch::difference, count}
+vanilla_benches! {dif_slice, rust_bench_btreeset::set_slice::difference, count, super::vecs}
+vanilla_benches! {dif_hash, std::collections::HashSet::difference, count, super::hash_sets}
+vanilla_benches! {dif_own_clone, super::clone_first, len}
+vanilla_benches! {dif_own_sub, super::difference_sub, len}
+vanilla_benches! {dif_own_collect, super::difference_collect, len}
+vanilla_benches! {dif_own_new, rust_bench_btreeset::set_new::sub, len}
+vanilla_benches! {dif_own_peeking, rust_bench_btreeset::set_peeking::sub, len}
+vanilla_benches! {dif_own_switch, rust_bench_btreeset::set_switch::sub, len}
+vanilla_benches! {dif_own_slice, rust_bench_btreeset::set_slice::sub, len, super::vecs}
+vanilla_benches! {dif_own_with, super::difference_with, len}
 vanilla_benches! {sub_old, rust_bench_btreeset::set_now::is_subset, clone}
 vanilla_benches! {sub_new, rust_bench_btreeset::set_new::is_subset, clone}
+vanilla_benches! {sub_switch, rust_bench_btreeset::set_switch::is_subset, clone}
+vanilla_benches! {sub_peeking, rust_bench_btreeset::set_peeking::is_subset, clone}
+vanilla_benches! {sub_slice, rust_bench_btreeset::set_slice::is_subset, clone, super::vecs}
+vanilla_benches! {sub_hash, std::collections::HashSet::is_subset, clone, super::hash_sets}
 
 vanilla_benches! {int_old, rust_bench_btreeset::set_now::intersection, count}
 vanilla_benches! {int_new, rust_bench_btreeset::set_new::intersection, count}
+vanilla_benches! {int_peeking, rust_bench_btreeset::set_peeking::intersection, count}
 vanilla_benches! {int_switch, rust_bench_btreeset::set_switch::intersection, count}
 vanilla_benches! {int_swivel, rust_bench_btreeset::set_swivel::intersection, count}
+vanilla_benches! {int_slice, rust_bench_btreeset::set_slice::intersection, count, super::vecs}
+vanilla_benches! {int_hash, std::collections::HashSet::intersection, count, super::hash_sets}
+
+vanilla_benches! {int_own_clone, super::clone_first, len}
+vanilla_benches! {int_own_bitand, super::intersection_bitand, len}
+vanilla_benches! {int_own_collect, super::intersection_collect, len}
+vanilla_benches! {int_own_new, rust_bench_btreeset::set_new::bitand, len}
+vanilla_benches! {int_own_peeking, rust_bench_btreeset::set_peeking::bitand, len}
+vanilla_benches! {int_own_switch, rust_bench_btreeset::set_switch::bitand, len}
+vanilla_benches! {int_own_swivel, rust_bench_btreeset::set_swivel::bitand, len}
+vanilla_benches! {int_own_slice, rust_bench_btreeset::set_slice::bitand, len, super::vecs}
+vanilla_benches! {int_own_with, super::intersect_with, len}
 
 vanilla_benches! {sym_old, rust_bench_btreeset::set_now::symmdiff, count}
 vanilla_benches! {sym_new, rust_bench_btreeset::set_new::symmdiff, count}
+vanilla_benches! {sym_peeking, rust_bench_btreeset::set_peeking::symmdiff, count}
+vanilla_benches! {sym_slice, rust_bench_btreeset::set_slice::symmdiff, count, super::vecs}
+vanilla_benches! {sym_own_bitxor, super::symmetric_difference_bitxor, len}
+vanilla_benches! {sym_own_new, rust_bench_btreeset::set_new::bitxor, len}
+vanilla_benches! {sym_own_peeking, rust_bench_btreeset::set_peeking::bitxor, len}
+vanilla_benches! {sym_own_slice, rust_bench_btreeset::set_slice::bitxor, len, super::vecs}
 vanilla_benches! {uni_old, rust_bench_btreeset::set_now::union, count}
 vanilla_benches! {uni_new, rust_bench_btreeset::set_new::union, count}
+vanilla_benches! {uni_peeking, rust_bench_btreeset::set_peeking::union, count}
+vanilla_benches! {uni_slice, rust_bench_btreeset::set_slice::union, count, super::vecs}
+vanilla_benches! {uni_hash, std::collections::HashSet::union, count, super::hash_sets}
+vanilla_benches! {uni_own_clone, super::clone_both, len}
+vanilla_benches! {uni_own_bitor, super::union_bitor, len}
+vanilla_benches! {uni_own_collect, super::union_collect, len}
+vanilla_benches! {uni_own_new, rust_bench_btreeset::set_new::bitor, len}
+vanilla_benches! {uni_own_peeking, rust_bench_btreeset::set_peeking::bitor, len}
+vanilla_benches! {uni_own_slice, rust_bench_btreeset::set_slice::bitor, len, super::vecs}
+vanilla_benches! {uni_own_with, super::union_with, len}
+
+vanilla_benches! {dif_next_peeking, rust_bench_btreeset::set_peeking::difference, count_by_next}
+vanilla_benches! {int_next_peeking, rust_bench_btreeset::set_peeking::intersection, count_by_next}
+vanilla_benches! {sym_next_peeking, rust_bench_btreeset::set_peeking::symmdiff, count_by_next}
+vanilla_benches! {uni_next_peeking, rust_bench_btreeset::set_peeking::union, count_by_next}
+vanilla_benches! {uni_next_slice, rust_bench_btreeset::set_slice::union, count_by_next, super::vecs}
+
+vanilla_benches! {dif_last_peeking, rust_bench_btreeset::set_peeking::difference, last}
+vanilla_benches! {dif_last_by_fold_peeking, rust_bench_btreeset::set_peeking::difference, last_by_fold}
+vanilla_benches! {int_last_peeking, rust_bench_btreeset::set_peeking::intersection, last}
+vanilla_benches! {int_last_by_fold_peeking, rust_bench_btreeset::set_peeking::intersection, last_by_fold}
+vanilla_benches! {sym_last_peeking, rust_bench_btreeset::set_peeking::symmdiff, last}
+vanilla_benches! {sym_last_by_fold_peeking, rust_bench_btreeset::set_peeking::symmdiff, last_by_fold}
+vanilla_benches! {uni_last_peeking, rust_bench_btreeset::set_peeking::union, last}
+vanilla_benches! {uni_last_by_fold_peeking, rust_bench_btreeset::set_peeking::union, last_by_fold}
+
+vanilla_benches! {cls_separately, super::classify_separately, count}
+vanilla_benches! {cls_peeking, rust_bench_btreeset::set_peeking::classify, count}
+vanilla_benches! {cls_changes_peeking, rust_bench_btreeset::set_peeking::classify_changes, count}
+
+vanilla_benches! {join_inner_get, super::inner_join_get, count, super::maps}
+vanilla_benches! {join_inner, rust_bench_btreeset::map_join::inner_join, count, super::maps}
+vanilla_benches! {join_semi, rust_bench_btreeset::map_join::semi_join, count, super::maps}
+vanilla_benches! {join_anti, rust_bench_btreeset::map_join::anti_join, count, super::maps}
+
+// A large set against a small one, whose size ratio runs up to
+// the tipping point between stitching and splicing.
+macro_rules! splice_benches {
+    ($mod_name: ident, $oper_name: path, $consume_name: ident) => {
+        splice_benches!($mod_name, $oper_name, $consume_name, std::convert::identity);
+    };
+    ($mod_name: ident, $oper_name: path, $consume_name: ident, $prepare: path) => {
+        set_benches!($mod_name, $oper_name, $consume_name,
+            random_1600_vs_100:     $prepare(super::swapped(super::random(100, 1_600)));
+            random_3200_vs_100:     $prepare(super::swapped(super::random(100, 3_200)));
+            random_4800_vs_100:     $prepare(super::swapped(super::random(100, 4_800)));
+            random_6400_vs_100:     $prepare(super::swapped(super::random(100, 6_400)));
+            random_10k_vs_100:      $prepare(super::swapped(super::random(100, 10_000)));
+            stagger_1600_vs_100:    $prepare(super::swapped(super::stagger(100, 16)));
+            stagger_3200_vs_100:    $prepare(super::swapped(super::stagger(100, 32)));
+            stagger_4800_vs_100:    $prepare(super::swapped(super::stagger(100, 48)));
+            stagger_6400_vs_100:    $prepare(super::swapped(super::stagger(100, 64)));
+            stagger_10k_vs_100:     $prepare(super::swapped(super::stagger(100, 100)));
+            superset_1600_vs_100:   $prepare(super::superset(16, 100));
+            superset_3200_vs_100:   $prepare(super::superset(32, 100));
+            superset_4800_vs_100:   $prepare(super::superset(48, 100));
+            superset_6400_vs_100:   $prepare(super::superset(64, 100));
+            superset_10k_vs_100:    $prepare(super::superset(100, 100));
+        );
+    }
+}
+
+splice_benches! {dif_tip_stitch, rust_bench_btreeset::set_peeking::difference_stitch, count}
+splice_benches! {dif_tip_splice, rust_bench_btreeset::set_peeking::difference_splice, count}
+splice_benches! {sym_tip_stitch, rust_bench_btreeset::set_peeking::symmdiff_stitch, count, super::swapped}
+splice_benches! {sym_tip_splice, rust_bench_btreeset::set_peeking::symmdiff_splice, count, super::swapped}
+splice_benches! {uni_tip_stitch, rust_bench_btreeset::set_peeking::union_stitch, count, super::swapped}
+splice_benches! {uni_tip_splice, rust_bench_btreeset::set_peeking::union_splice, count, super::swapped}
+
+macro_rules! page_benches {
+    ($mod_name: ident, $oper_name: path) => {
+        set_benches!($mod_name, $oper_name, clone,
+            random_1m_vs_1m:    super::random(1_000_000, 1_000_000);
+            stagger_10k_vs_1m:  super::stagger(10_000, 100);
+            stagger_1m_vs_10k:  { let [a, b] = super::stagger(10_000, 100); [b, a] };
+        );
+    }
+}
+
+page_benches! {dif_paged_peeking, super::difference_paged}
+page_benches! {dif_walked_peeking, super::difference_walked}
+page_benches! {int_paged_peeking, super::intersection_paged}
+page_benches! {int_walked_peeking, super::intersection_walked}
+page_benches! {uni_paged_peeking, super::union_paged}
+page_benches! {uni_walked_peeking, super::union_walked}
 
 stagger_benches! {int_stagger_old, rust_bench_btreeset::set_now::intersection, count}
 stagger_benches! {int_stagger_new, rust_bench_btreeset::set_peeking::intersection, count}
 stagger_benches! {int_stagger_search, rust_bench_btreeset::set_peeking::intersection_search, count}
 stagger_benches! {int_stagger_stitch, rust_bench_btreeset::set_peeking::intersection_stitch, count}
+stagger_benches! {int_stagger_swivel, rust_bench_btreeset::set_swivel::intersection, count}
+stagger_benches! {int_stagger_slice, rust_bench_btreeset::set_slice::intersection, count, super::vecs}
+stagger_benches! {int_stagger_hash, std::collections::HashSet::intersection, count, super::hash_sets}
+stagger_benches! {int_stagger_slice_gallop, rust_bench_btreeset::set_slice::intersection_gallop, count, super::vecs}
+stagger_benches! {int_stagger_slice_search, rust_bench_btreeset::set_slice::intersection_search, count, super::vecs}
+stagger_benches! {int_stagger_slice_stitch, rust_bench_btreeset::set_slice::intersection_stitch, count, super::vecs}
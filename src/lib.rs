@@ -1,5 +1,8 @@
+pub mod map_join;
 pub mod set_new;
 pub mod set_now;
 pub mod set_peeking;
+pub mod set_slice;
 pub mod set_switch;
 pub mod set_swivel;
+pub mod sorted_view;
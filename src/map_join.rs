@@ -0,0 +1,328 @@
+// Joins on the keys of two BTreeMaps, with the strategies of the set operations.
+use core::cmp::min;
+use core::cmp::Ordering::{Equal, Greater, Less};
+use core::fmt::{self, Debug};
+use core::iter::{FusedIterator, Peekable};
+use std::collections::btree_map::Iter;
+use std::collections::BTreeMap;
+
+/// A lazy iterator producing the keys present in both `BTreeMap`s,
+/// with the value each map holds for it.
+///
+/// This `struct` is created by the [`inner_join`] function.
+/// See its documentation for more.
+///
+/// [`inner_join`]: fn.inner_join.html
+pub struct InnerJoin<'a, K: 'a, V1: 'a, V2: 'a> {
+    inner: JoinInner<'a, K, V1, V2>,
+}
+
+/// A lazy iterator producing the entries of a `BTreeMap`
+/// whose key is present in another `BTreeMap`.
+///
+/// This `struct` is created by the [`semi_join`] function.
+/// See its documentation for more.
+///
+/// [`semi_join`]: fn.semi_join.html
+pub struct SemiJoin<'a, K: 'a, V1: 'a, V2: 'a> {
+    inner: JoinInner<'a, K, V1, V2>,
+}
+
+#[derive(Debug)]
+enum JoinInner<'a, K: 'a, V1: 'a, V2: 'a> {
+    Stitch {
+        // iterate similarly sized maps jointly, spotting matches along the way
+        a_iter: Iter<'a, K, V1>,
+        b_iter: Iter<'a, K, V2>,
+        max_len: usize,
+    },
+    SearchB {
+        // iterate the small map a, look up in the large map b
+        a_iter: Iter<'a, K, V1>,
+        b_map: &'a BTreeMap<K, V2>,
+        max_len: usize,
+    },
+    SearchA {
+        // iterate the small map b, look up in the large map a
+        b_iter: Iter<'a, K, V2>,
+        a_map: &'a BTreeMap<K, V1>,
+        max_len: usize,
+    },
+}
+
+impl<K: Debug, V1: Debug, V2: Debug> Debug for InnerJoin<'_, K, V1, V2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("InnerJoin").field(&self.inner).finish()
+    }
+}
+
+impl<K: Debug, V1: Debug, V2: Debug> Debug for SemiJoin<'_, K, V1, V2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SemiJoin").field(&self.inner).finish()
+    }
+}
+
+/// A lazy iterator producing the entries of a `BTreeMap`
+/// whose key is absent from another `BTreeMap`.
+///
+/// This `struct` is created by the [`anti_join`] function.
+/// See its documentation for more.
+///
+/// [`anti_join`]: fn.anti_join.html
+pub struct AntiJoin<'a, K: 'a, V1: 'a, V2: 'a> {
+    inner: AntiJoinInner<'a, K, V1, V2>,
+}
+
+#[derive(Debug)]
+enum AntiJoinInner<'a, K: 'a, V1: 'a, V2: 'a> {
+    Stitch {
+        // iterate both maps jointly, spotting matches along the way
+        a_iter: Iter<'a, K, V1>,
+        b_iter: Peekable<Iter<'a, K, V2>>,
+    },
+    Search {
+        // iterate the small map a, look up in the large map b
+        a_iter: Iter<'a, K, V1>,
+        b_map: &'a BTreeMap<K, V2>,
+    },
+}
+
+impl<K: Debug, V1: Debug, V2: Debug> Debug for AntiJoin<'_, K, V1, V2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AntiJoin").field(&self.inner).finish()
+    }
+}
+
+// This constant is used by functions that compare two maps.
+// It estimates the relative size at which searching performs better
+// than iterating, like its namesake in the set modules.
+const ITER_PERFORMANCE_TIPPING_SIZE_DIFF: usize = 16;
+
+impl<'a, K: Ord, V1, V2> JoinInner<'a, K, V1, V2> {
+    fn new(a: &'a BTreeMap<K, V1>, b: &'a BTreeMap<K, V2>) -> Self {
+        if a.len() <= b.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            JoinInner::SearchB {
+                a_iter: a.iter(),
+                b_map: b,
+                max_len: a.len(),
+            }
+        } else if b.len() <= a.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            JoinInner::SearchA {
+                b_iter: b.iter(),
+                a_map: a,
+                max_len: b.len(),
+            }
+        } else {
+            JoinInner::Stitch {
+                a_iter: a.iter(),
+                b_iter: b.iter(),
+                max_len: min(a.len(), b.len()),
+            }
+        }
+    }
+
+    /// Core of InnerJoin and SemiJoin.
+    fn next(&mut self) -> Option<(&'a K, &'a V1, &'a V2)> {
+        match self {
+            JoinInner::Stitch { a_iter, b_iter, .. } => {
+                let mut a_next = a_iter.next()?;
+                let mut b_next = b_iter.next()?;
+                loop {
+                    match a_next.0.cmp(b_next.0) {
+                        Less => a_next = a_iter.next()?,
+                        Greater => b_next = b_iter.next()?,
+                        Equal => return Some((a_next.0, a_next.1, b_next.1)),
+                    }
+                }
+            }
+            JoinInner::SearchB { a_iter, b_map, .. } => loop {
+                let (key, a_value) = a_iter.next()?;
+                if let Some(b_value) = b_map.get(key) {
+                    return Some((key, a_value, b_value));
+                }
+            },
+            JoinInner::SearchA { b_iter, a_map, .. } => loop {
+                let (key, b_value) = b_iter.next()?;
+                if let Some((key, a_value)) = a_map.get_key_value(key) {
+                    return Some((key, a_value, b_value));
+                }
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            JoinInner::Stitch { max_len, .. } => (0, Some(*max_len)),
+            JoinInner::SearchB { max_len, .. } => (0, Some(*max_len)),
+            JoinInner::SearchA { max_len, .. } => (0, Some(*max_len)),
+        }
+    }
+}
+
+impl<K, V1, V2> Clone for JoinInner<'_, K, V1, V2> {
+    fn clone(&self) -> Self {
+        match self {
+            JoinInner::Stitch {
+                a_iter,
+                b_iter,
+                max_len,
+            } => JoinInner::Stitch {
+                a_iter: a_iter.clone(),
+                b_iter: b_iter.clone(),
+                max_len: *max_len,
+            },
+            JoinInner::SearchB {
+                a_iter,
+                b_map,
+                max_len,
+            } => JoinInner::SearchB {
+                a_iter: a_iter.clone(),
+                b_map,
+                max_len: *max_len,
+            },
+            JoinInner::SearchA {
+                b_iter,
+                a_map,
+                max_len,
+            } => JoinInner::SearchA {
+                b_iter: b_iter.clone(),
+                a_map,
+                max_len: *max_len,
+            },
+        }
+    }
+}
+
+impl<K, V1, V2> Clone for InnerJoin<'_, K, V1, V2> {
+    fn clone(&self) -> Self {
+        InnerJoin {
+            inner: self.inner.clone(),
+        }
+    }
+}
+impl<'a, K: Ord, V1, V2> Iterator for InnerJoin<'a, K, V1, V2> {
+    type Item = (&'a K, &'a V1, &'a V2);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V1, &'a V2)> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K: Ord, V1, V2> FusedIterator for InnerJoin<'_, K, V1, V2> {}
+
+impl<K, V1, V2> Clone for SemiJoin<'_, K, V1, V2> {
+    fn clone(&self) -> Self {
+        SemiJoin {
+            inner: self.inner.clone(),
+        }
+    }
+}
+impl<'a, K: Ord, V1, V2> Iterator for SemiJoin<'a, K, V1, V2> {
+    type Item = (&'a K, &'a V1);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V1)> {
+        self.inner.next().map(|(key, a_value, _)| (key, a_value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K: Ord, V1, V2> FusedIterator for SemiJoin<'_, K, V1, V2> {}
+
+impl<K, V1, V2> Clone for AntiJoin<'_, K, V1, V2> {
+    fn clone(&self) -> Self {
+        AntiJoin {
+            inner: match &self.inner {
+                AntiJoinInner::Stitch { a_iter, b_iter } => AntiJoinInner::Stitch {
+                    a_iter: a_iter.clone(),
+                    b_iter: b_iter.clone(),
+                },
+                AntiJoinInner::Search { a_iter, b_map } => AntiJoinInner::Search {
+                    a_iter: a_iter.clone(),
+                    b_map,
+                },
+            },
+        }
+    }
+}
+impl<'a, K: Ord, V1, V2> Iterator for AntiJoin<'a, K, V1, V2> {
+    type Item = (&'a K, &'a V1);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V1)> {
+        match &mut self.inner {
+            AntiJoinInner::Stitch { a_iter, b_iter } => loop {
+                let a_next = a_iter.next()?;
+                loop {
+                    match b_iter.peek().map_or(Less, |b_next| a_next.0.cmp(b_next.0)) {
+                        Less => return Some(a_next),
+                        Equal => {
+                            b_iter.next();
+                            break;
+                        }
+                        Greater => {
+                            b_iter.next();
+                        }
+                    }
+                }
+            },
+            AntiJoinInner::Search { a_iter, b_map } => loop {
+                let a_next = a_iter.next()?;
+                if !b_map.contains_key(a_next.0) {
+                    return Some(a_next);
+                }
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            AntiJoinInner::Stitch { a_iter, .. } => (0, Some(a_iter.len())),
+            AntiJoinInner::Search { a_iter, .. } => (0, Some(a_iter.len())),
+        }
+    }
+}
+
+impl<K: Ord, V1, V2> FusedIterator for AntiJoin<'_, K, V1, V2> {}
+
+pub fn inner_join<'a, K: Ord, V1, V2>(
+    a: &'a BTreeMap<K, V1>,
+    b: &'a BTreeMap<K, V2>,
+) -> InnerJoin<'a, K, V1, V2> {
+    InnerJoin {
+        inner: JoinInner::new(a, b),
+    }
+}
+
+pub fn semi_join<'a, K: Ord, V1, V2>(
+    a: &'a BTreeMap<K, V1>,
+    b: &'a BTreeMap<K, V2>,
+) -> SemiJoin<'a, K, V1, V2> {
+    SemiJoin {
+        inner: JoinInner::new(a, b),
+    }
+}
+
+pub fn anti_join<'a, K: Ord, V1, V2>(
+    a: &'a BTreeMap<K, V1>,
+    b: &'a BTreeMap<K, V2>,
+) -> AntiJoin<'a, K, V1, V2> {
+    AntiJoin {
+        inner: if a.len() <= b.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            AntiJoinInner::Search {
+                a_iter: a.iter(),
+                b_map: b,
+            }
+        } else {
+            AntiJoinInner::Stitch {
+                a_iter: a.iter(),
+                b_iter: b.iter().peekable(),
+            }
+        },
+    }
+}
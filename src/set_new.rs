@@ -2,10 +2,14 @@
 use core::cmp::Ordering::{Equal, Greater, Less};
 use core::cmp::{max, min};
 use core::fmt::{self, Debug};
-use core::iter::{FusedIterator, Peekable};
-use std::collections::btree_set::Iter;
+use core::iter::{self, FusedIterator, Peekable};
+use core::mem;
+use core::ops::Bound::{Excluded, Unbounded};
+use std::collections::btree_set::{Iter, Range};
 use std::collections::BTreeSet;
 
+use crate::sorted_view::from_sorted;
+
 /*
 // This is pretty much entirely stolen from TreeSet, since BTreeMap has an identical interface
 // to TreeMap
@@ -122,61 +126,241 @@ pub struct Range<'a, T: 'a> {
 /// Core of SymmetricDifference and Union.
 /// More efficient than btree.map.MergeIter,
 /// and crucially for SymmetricDifference, nexts() reports on both sides.
+/// Elements of one side lower or greater than any on the other side,
+/// and the elements remaining once one side is exhausted,
+/// are streamed without comparisons.
 #[derive(Clone)]
 struct MergeIterInner<I>
 where
     I: Iterator,
     I::Item: Copy,
 {
+    prefix: I,
+    prefix_side: MergeIterSide,
     a: I,
     b: I,
-    peeked: Option<MergeIterPeeked<I>>,
+    suffix: I,
+    suffix_side: MergeIterSide,
+    state: MergeIterState<I>,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum MergeIterSide {
+    A,
+    B,
 }
 
 #[derive(Copy, Clone, Debug)]
-enum MergeIterPeeked<I: Iterator> {
-    A(I::Item),
-    B(I::Item),
+enum MergeIterState<I: Iterator> {
+    Prefix,           // stream the prefix
+    Compare,          // compare the next elements on both sides
+    PeekedA(I::Item), // compare with an element of a read ahead
+    PeekedB(I::Item), // compare with an element of b read ahead
+    StreamA,          // stream the remainder of a, then of b
+    StreamB,          // stream the remainder of b, then of a
+    Suffix,           // stream the suffix
+}
+
+impl MergeIterSide {
+    fn other(self) -> Self {
+        match self {
+            MergeIterSide::A => MergeIterSide::B,
+            MergeIterSide::B => MergeIterSide::A,
+        }
+    }
+
+    fn report<T>(self, next: Option<T>) -> (Option<T>, Option<T>) {
+        match self {
+            MergeIterSide::A => (next, None),
+            MergeIterSide::B => (None, next),
+        }
+    }
+}
+
+impl<'a, T: Ord> MergeIterInner<Range<'a, T>> {
+    fn new(a_set: &'a BTreeSet<T>, b_set: &'a BTreeSet<T>) -> Self {
+        let (a_min, a_max, b_min, b_max) = match (
+            a_set.iter().next(),
+            a_set.iter().next_back(),
+            b_set.iter().next(),
+            b_set.iter().next_back(),
+        ) {
+            (Some(a_min), Some(a_max), Some(b_min), Some(b_max)) => (a_min, a_max, b_min, b_max),
+            _ => {
+                // One side is empty, so stream the other.
+                let empty = if a_set.is_empty() { a_set } else { b_set };
+                return MergeIterInner {
+                    prefix: empty.range(..),
+                    prefix_side: MergeIterSide::A,
+                    a: a_set.range(..),
+                    b: b_set.range(..),
+                    suffix: empty.range(..),
+                    suffix_side: MergeIterSide::A,
+                    state: MergeIterState::StreamA,
+                };
+            }
+        };
+        if a_max < b_min || b_max < a_min {
+            // Disjoint ranges, so stream one side after the other.
+            return MergeIterInner {
+                prefix: a_set.range(..a_min),
+                prefix_side: MergeIterSide::A,
+                a: a_set.range(..),
+                b: b_set.range(..),
+                suffix: a_set.range(..a_min),
+                suffix_side: MergeIterSide::A,
+                state: if a_max < b_min {
+                    MergeIterState::StreamA
+                } else {
+                    MergeIterState::StreamB
+                },
+            };
+        }
+        let (prefix, prefix_side, lo) = if a_min < b_min {
+            (a_set.range(..b_min), MergeIterSide::A, b_min)
+        } else {
+            (b_set.range(..a_min), MergeIterSide::B, a_min)
+        };
+        let (suffix, suffix_side, hi) = if a_max > b_max {
+            (
+                a_set.range((Excluded(b_max), Unbounded)),
+                MergeIterSide::A,
+                b_max,
+            )
+        } else {
+            (
+                b_set.range((Excluded(a_max), Unbounded)),
+                MergeIterSide::B,
+                a_max,
+            )
+        };
+        MergeIterInner {
+            prefix,
+            prefix_side,
+            a: a_set.range(lo..=hi),
+            b: b_set.range(lo..=hi),
+            suffix,
+            suffix_side,
+            state: MergeIterState::Prefix,
+        }
+    }
 }
 
 impl<I> MergeIterInner<I>
 where
-    I: ExactSizeIterator + FusedIterator,
+    I: FusedIterator,
     I::Item: Copy + Ord,
 {
-    fn new(a: I, b: I) -> Self {
-        MergeIterInner { a, b, peeked: None }
-    }
-
     fn nexts(&mut self) -> (Option<I::Item>, Option<I::Item>) {
-        let mut a_next = match self.peeked {
-            Some(MergeIterPeeked::A(next)) => Some(next),
-            _ => self.a.next(),
-        };
-        let mut b_next = match self.peeked {
-            Some(MergeIterPeeked::B(next)) => Some(next),
-            _ => self.b.next(),
-        };
-        let ord = match (a_next, b_next) {
-            (None, None) => Equal,
-            (_, None) => Less,
-            (None, _) => Greater,
-            (Some(a1), Some(b1)) => a1.cmp(&b1),
+        let (mut a_next, mut b_next) = match self.state {
+            MergeIterState::Prefix => match self.prefix.next() {
+                Some(next) => return self.prefix_side.report(Some(next)),
+                None => (self.a.next(), self.b.next()),
+            },
+            MergeIterState::Compare => (self.a.next(), self.b.next()),
+            MergeIterState::PeekedA(a1) => (Some(a1), self.b.next()),
+            MergeIterState::PeekedB(b1) => (self.a.next(), Some(b1)),
+            MergeIterState::StreamA => match self.a.next() {
+                Some(a1) => return (Some(a1), None),
+                None => (None, self.b.next()),
+            },
+            MergeIterState::StreamB => match self.b.next() {
+                Some(b1) => return (None, Some(b1)),
+                None => (self.a.next(), None),
+            },
+            MergeIterState::Suffix => return self.suffix_side.report(self.suffix.next()),
         };
-        self.peeked = match ord {
-            Less => b_next.take().map(MergeIterPeeked::B),
-            Equal => None,
-            Greater => a_next.take().map(MergeIterPeeked::A),
+        self.state = match (a_next, b_next) {
+            (Some(a1), Some(b1)) => match a1.cmp(&b1) {
+                Less => {
+                    b_next = None;
+                    MergeIterState::PeekedB(b1)
+                }
+                Equal => MergeIterState::Compare,
+                Greater => {
+                    a_next = None;
+                    MergeIterState::PeekedA(a1)
+                }
+            },
+            (Some(_), None) => MergeIterState::StreamA,
+            (None, Some(_)) => MergeIterState::StreamB,
+            (None, None) => {
+                self.state = MergeIterState::Suffix;
+                return self.suffix_side.report(self.suffix.next());
+            }
         };
         (a_next, b_next)
     }
 
-    fn lens(&self) -> (usize, usize) {
-        match self.peeked {
-            Some(MergeIterPeeked::A(_)) => (1 + self.a.len(), self.b.len()),
-            Some(MergeIterPeeked::B(_)) => (self.a.len(), 1 + self.b.len()),
-            _ => (self.a.len(), self.b.len()),
+    /// Folds the pairs that nexts() would report, until all parts are exhausted,
+    /// keeping the next element of each side at hand instead of in `state`.
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (Option<I::Item>, Option<I::Item>)) -> B,
+    {
+        let MergeIterInner {
+            prefix,
+            prefix_side,
+            mut a,
+            mut b,
+            suffix,
+            suffix_side,
+            state,
+        } = self;
+        let mut acc = init;
+        let (mut a_next, mut b_next) = match state {
+            MergeIterState::Prefix => {
+                acc = prefix.fold(acc, |acc, next| f(acc, prefix_side.report(Some(next))));
+                (a.next(), b.next())
+            }
+            MergeIterState::Compare => (a.next(), b.next()),
+            MergeIterState::PeekedA(a1) => (Some(a1), b.next()),
+            MergeIterState::PeekedB(b1) => (a.next(), Some(b1)),
+            MergeIterState::StreamA => match a.next() {
+                Some(a1) => (Some(a1), None),
+                None => (None, b.next()),
+            },
+            MergeIterState::StreamB => match b.next() {
+                Some(b1) => (None, Some(b1)),
+                None => (a.next(), None),
+            },
+            MergeIterState::Suffix => (None, None),
+        };
+        loop {
+            match (a_next, b_next) {
+                (Some(a1), Some(b1)) => match a1.cmp(&b1) {
+                    Less => {
+                        acc = f(acc, (a_next, None));
+                        a_next = a.next();
+                    }
+                    Equal => {
+                        acc = f(acc, (a_next, b_next));
+                        a_next = a.next();
+                        b_next = b.next();
+                    }
+                    Greater => {
+                        acc = f(acc, (None, b_next));
+                        b_next = b.next();
+                    }
+                },
+                // Stream the remainder of one side, then of the other,
+                // which only has elements left if the sides are disjoint.
+                (Some(_), None) => {
+                    acc = f(acc, (a_next, None));
+                    acc = a.fold(acc, |acc, a1| f(acc, (Some(a1), None)));
+                    acc = b.fold(acc, |acc, b1| f(acc, (None, Some(b1))));
+                    break;
+                }
+                (None, Some(_)) => {
+                    acc = f(acc, (None, b_next));
+                    acc = b.fold(acc, |acc, b1| f(acc, (None, Some(b1))));
+                    acc = a.fold(acc, |acc, a1| f(acc, (Some(a1), None)));
+                    break;
+                }
+                (None, None) => break,
+            }
         }
+        suffix.fold(acc, |acc, next| f(acc, suffix_side.report(Some(next))))
     }
 }
 
@@ -187,12 +371,226 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("MergeIterInner")
+            .field(&self.prefix)
             .field(&self.a)
             .field(&self.b)
+            .field(&self.suffix)
             .finish()
     }
 }
 
+/// Alternative core of SymmetricDifference and Union, for a small set
+/// against a large set. Walks the small set and streams the segments
+/// of the large set in between its elements, comparing only at the boundaries.
+#[derive(Debug)]
+struct MergeSpliceInner<'a, T: 'a> {
+    small_iter: Range<'a, T>,
+    small_side: MergeIterSide,
+    small_next: Option<&'a T>, // upper bound of the current segment
+    large_set: &'a BTreeSet<T>,
+    segment: Range<'a, T>,
+}
+
+impl<'a, T: Ord> MergeSpliceInner<'a, T> {
+    fn new(
+        small_set: &'a BTreeSet<T>,
+        small_side: MergeIterSide,
+        large_set: &'a BTreeSet<T>,
+    ) -> Self {
+        let mut small_iter = small_set.range(..);
+        let small_next = small_iter.next();
+        MergeSpliceInner {
+            small_iter,
+            small_side,
+            small_next,
+            large_set,
+            segment: match small_next {
+                Some(small_next) => large_set.range(..small_next),
+                None => large_set.range(..),
+            },
+        }
+    }
+
+    fn nexts(&mut self) -> (Option<&'a T>, Option<&'a T>) {
+        if let Some(large_next) = self.segment.next() {
+            return self.small_side.other().report(Some(large_next));
+        }
+        let small_next = match self.small_next {
+            Some(small_next) => small_next,
+            None => return (None, None),
+        };
+        self.small_next = self.small_iter.next();
+        self.segment = match self.small_next {
+            Some(upper) => self
+                .large_set
+                .range((Excluded(small_next), Excluded(upper))),
+            None => self.large_set.range((Excluded(small_next), Unbounded)),
+        };
+        let large_match = self.large_set.get(small_next);
+        match self.small_side {
+            MergeIterSide::A => (Some(small_next), large_match),
+            MergeIterSide::B => (large_match, Some(small_next)),
+        }
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (Option<&'a T>, Option<&'a T>)) -> B,
+    {
+        let MergeSpliceInner {
+            mut small_iter,
+            small_side,
+            mut small_next,
+            large_set,
+            segment,
+        } = self;
+        let large_side = small_side.other();
+        let mut acc = segment.fold(init, |acc, large_next| {
+            f(acc, large_side.report(Some(large_next)))
+        });
+        while let Some(lower) = small_next {
+            small_next = small_iter.next();
+            let segment = match small_next {
+                Some(upper) => large_set.range((Excluded(lower), Excluded(upper))),
+                None => large_set.range((Excluded(lower), Unbounded)),
+            };
+            let large_match = large_set.get(lower);
+            acc = f(
+                acc,
+                match small_side {
+                    MergeIterSide::A => (Some(lower), large_match),
+                    MergeIterSide::B => (large_match, Some(lower)),
+                },
+            );
+            acc = segment.fold(acc, |acc, large_next| {
+                f(acc, large_side.report(Some(large_next)))
+            });
+        }
+        acc
+    }
+}
+
+impl<T> Clone for MergeSpliceInner<'_, T> {
+    fn clone(&self) -> Self {
+        MergeSpliceInner {
+            small_iter: self.small_iter.clone(),
+            small_side: self.small_side,
+            small_next: self.small_next,
+            large_set: self.large_set,
+            segment: self.segment.clone(),
+        }
+    }
+}
+
+/// Choice between the cores of SymmetricDifference and Union.
+#[derive(Debug)]
+enum MergeInner<'a, T: 'a> {
+    Stitch(MergeIterInner<Range<'a, T>>), // merge sets of similar size
+    Splice(MergeSpliceInner<'a, T>),      // splice a small set into a large set
+}
+
+impl<'a, T: Ord> MergeInner<'a, T> {
+    fn new(a_set: &'a BTreeSet<T>, b_set: &'a BTreeSet<T>) -> Self {
+        if a_set.len() <= b_set.len() / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF {
+            MergeInner::Splice(MergeSpliceInner::new(a_set, MergeIterSide::A, b_set))
+        } else if b_set.len() <= a_set.len() / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF {
+            MergeInner::Splice(MergeSpliceInner::new(b_set, MergeIterSide::B, a_set))
+        } else {
+            MergeInner::Stitch(MergeIterInner::new(a_set, b_set))
+        }
+    }
+
+    fn nexts(&mut self) -> (Option<&'a T>, Option<&'a T>) {
+        match self {
+            MergeInner::Stitch(merge) => merge.nexts(),
+            MergeInner::Splice(splice) => splice.nexts(),
+        }
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, (Option<&'a T>, Option<&'a T>)) -> B,
+    {
+        match self {
+            MergeInner::Stitch(merge) => merge.fold(init, f),
+            MergeInner::Splice(splice) => splice.fold(init, f),
+        }
+    }
+}
+
+impl<T> Clone for MergeInner<'_, T> {
+    fn clone(&self) -> Self {
+        match self {
+            MergeInner::Stitch(merge) => MergeInner::Stitch(merge.clone()),
+            MergeInner::Splice(splice) => MergeInner::Splice(splice.clone()),
+        }
+    }
+}
+
+/// The number of elements left on either side of an operation, counting down
+/// as the operation takes elements from either side. Difference and
+/// Intersection don't take the elements of the large set they search in,
+/// so these are upper bounds for those.
+#[derive(Clone, Copy, Debug)]
+struct MergeLens {
+    a_len: usize,
+    b_len: usize,
+}
+
+impl MergeLens {
+    fn took<T>(&mut self, (a_next, b_next): &(Option<T>, Option<T>)) {
+        if a_next.is_some() {
+            self.a_len -= 1;
+        }
+        if b_next.is_some() {
+            self.b_len -= 1;
+        }
+    }
+
+    /// Counts the element taken from side a, if any, and passes it on.
+    fn took_a<T>(&mut self, a_next: Option<T>) -> Option<T> {
+        if a_next.is_some() {
+            self.a_len -= 1;
+        }
+        a_next
+    }
+
+    /// Counts the element taken from side b, if any, and passes it on.
+    fn took_b<T>(&mut self, b_next: Option<T>) -> Option<T> {
+        if b_next.is_some() {
+            self.b_len -= 1;
+        }
+        b_next
+    }
+
+    fn max_len(&self) -> usize {
+        // No checked_add, because even if a and b refer to the same set,
+        // and T is an empty type, the storage overhead of sets limits
+        // the number of elements to less than half the range of usize.
+        self.a_len + self.b_len
+    }
+
+    /// Bounds the length of the difference of what's left.
+    fn difference_hint(&self) -> (usize, Option<usize>) {
+        (self.a_len.saturating_sub(self.b_len), Some(self.a_len))
+    }
+
+    /// Bounds the length of the intersection of what's left.
+    fn intersection_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(min(self.a_len, self.b_len)))
+    }
+
+    /// Bounds the length of the union of what's left.
+    fn union_hint(&self) -> (usize, Option<usize>) {
+        (max(self.a_len, self.b_len), Some(self.max_len()))
+    }
+
+    /// Bounds the length of the symmetric difference of what's left.
+    fn symmdiff_hint(&self) -> (usize, Option<usize>) {
+        (self.a_len.abs_diff(self.b_len), Some(self.max_len()))
+    }
+}
+
 /// A lazy iterator producing elements in the difference of `BTreeSet`s.
 ///
 /// This `struct` is created by the [`difference`] method on [`BTreeSet`].
@@ -202,19 +600,35 @@ where
 /// [`difference`]: struct.BTreeSet.html#method.difference
 pub struct Difference<'a, T: 'a> {
     inner: DifferenceInner<'a, T>,
+    lens: MergeLens,
 }
 #[derive(Debug)]
 enum DifferenceInner<'a, T: 'a> {
     Stitch {
-        // iterate all of self and some of other, spotting matches along the way
-        self_iter: Iter<'a, T>,
-        other_iter: Peekable<Iter<'a, T>>,
+        // stream self's elements outside other's range, and iterate the rest
+        // of self and the part of other within self's range jointly,
+        // spotting matches along the way
+        self_prefix: Range<'a, T>,
+        self_iter: Range<'a, T>,
+        self_suffix: Range<'a, T>,
+        other_iter: Peekable<Range<'a, T>>,
     },
     Search {
-        // iterate a small set, look up in the large set
-        self_iter: Iter<'a, T>,
+        // stream self's elements outside other's range, and iterate the rest
+        // of the small self, looking up in the large other set
+        self_prefix: Range<'a, T>,
+        self_iter: Range<'a, T>,
+        self_suffix: Range<'a, T>,
         other_set: &'a BTreeSet<T>,
     },
+    Splice {
+        // iterate the part of the small other set within self's range,
+        // streaming the segments of self in between its elements
+        other_iter: Range<'a, T>,
+        other_next: Option<&'a T>, // upper bound of the current segment
+        self_set: &'a BTreeSet<T>,
+        self_segment: Range<'a, T>,
+    },
     Iterate(Iter<'a, T>), // simply stream self's elements
 }
 
@@ -231,11 +645,16 @@ impl<T: fmt::Debug> fmt::Debug for Difference<'_, T> {
 ///
 /// [`BTreeSet`]: struct.BTreeSet.html
 /// [`symmetric_difference`]: struct.BTreeSet.html#method.symmetric_difference
-pub struct SymmetricDifference<'a, T: 'a>(MergeIterInner<Iter<'a, T>>);
+pub struct SymmetricDifference<'a, T: 'a> {
+    merge: MergeInner<'a, T>,
+    lens: MergeLens,
+}
 
 impl<T: fmt::Debug> fmt::Debug for SymmetricDifference<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("SymmetricDifference").field(&self.0).finish()
+        f.debug_tuple("SymmetricDifference")
+            .field(&self.merge)
+            .finish()
     }
 }
 
@@ -248,17 +667,20 @@ impl<T: fmt::Debug> fmt::Debug for SymmetricDifference<'_, T> {
 /// [`intersection`]: struct.BTreeSet.html#method.intersection
 pub struct Intersection<'a, T: 'a> {
     inner: IntersectionInner<'a, T>,
+    lens: MergeLens,
 }
 #[derive(Debug)]
 enum IntersectionInner<'a, T: 'a> {
     Stitch {
-        // iterate similarly sized sets jointly, spotting matches along the way
-        a: Iter<'a, T>,
-        b: Iter<'a, T>,
+        // iterate the overlapping parts of similarly sized sets jointly,
+        // spotting matches along the way
+        a: Range<'a, T>,
+        b: Range<'a, T>,
     },
     Search {
-        // iterate a small set, look up in the large set
-        small_iter: Iter<'a, T>,
+        // iterate the part of a small set overlapping the large set,
+        // look up in the large set
+        small_iter: Range<'a, T>,
         large_set: &'a BTreeSet<T>,
     },
     Answer(Option<&'a T>), // return a specific value or emptiness
@@ -277,11 +699,14 @@ impl<T: fmt::Debug> fmt::Debug for Intersection<'_, T> {
 ///
 /// [`BTreeSet`]: struct.BTreeSet.html
 /// [`union`]: struct.BTreeSet.html#method.union
-pub struct Union<'a, T: 'a>(MergeIterInner<Iter<'a, T>>);
+pub struct Union<'a, T: 'a> {
+    merge: MergeInner<'a, T>,
+    lens: MergeLens,
+}
 
 impl<T: fmt::Debug> fmt::Debug for Union<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("Union").field(&self.0).finish()
+        f.debug_tuple("Union").field(&self.merge).finish()
     }
 }
 
@@ -293,6 +718,13 @@ impl<T: fmt::Debug> fmt::Debug for Union<'_, T> {
 // and it's a power of two to make that division cheap.
 const ITER_PERFORMANCE_TIPPING_SIZE_DIFF: usize = 16;
 
+// Likewise, the relative size at which streaming segments of the large set
+// in between the elements of the small set performs better than merging.
+// Each segment costs a few searches, and iterating a Range is slower than
+// iterating a whole set, so it only pays off for long segments. Tuned on
+// the benchmarks of set_peeking, which splices the same way.
+const SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF: usize = 64;
+
 /*
 impl<T: Ord> BTreeSet<T> {
     /// Makes a new `BTreeSet` with a reasonable choice of B.
@@ -368,6 +800,9 @@ trait JustToIndentAsMuch<T> {
     fn intersection<'a>(&'a self, other: &'a BTreeSet<T>) -> Intersection<'a, T>;
     fn union<'a>(&'a self, other: &'a BTreeSet<T>) -> Union<'a, T>;
     fn is_subset(&self, other: &BTreeSet<T>) -> bool;
+    fn intersect_with(&mut self, other: &BTreeSet<T>);
+    fn difference_with(&mut self, other: &BTreeSet<T>);
+    fn union_with(&mut self, other: BTreeSet<T>);
 }
 impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
     fn difference<'a>(&'a self, other: &'a BTreeSet<T>) -> Difference<'a, T> {
@@ -376,21 +811,17 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
         {
             (self_min, self_max)
         } else {
-            return Difference {
-                inner: DifferenceInner::Iterate(self.iter()),
-            };
+            return Difference::of_sets(DifferenceInner::Iterate(self.iter()), self, other);
         };
         let (other_min, other_max) = if let (Some(other_min), Some(other_max)) =
             (other.iter().next(), other.iter().next_back())
         {
             (other_min, other_max)
         } else {
-            return Difference {
-                inner: DifferenceInner::Iterate(self.iter()),
-            };
+            return Difference::of_sets(DifferenceInner::Iterate(self.iter()), self, other);
         };
-        Difference {
-            inner: match (self_min.cmp(other_max), self_max.cmp(other_min)) {
+        Difference::of_sets(
+            match (self_min.cmp(other_max), self_max.cmp(other_min)) {
                 (Greater, _) | (_, Less) => DifferenceInner::Iterate(self.iter()),
                 (Equal, _) => {
                     let mut self_iter = self.iter();
@@ -404,16 +835,35 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
                 }
                 _ if self.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF => {
                     DifferenceInner::Search {
-                        self_iter: self.iter(),
+                        self_prefix: self.range(..other_min),
+                        self_iter: self.range(other_min..=other_max),
+                        self_suffix: self.range((Excluded(other_max), Unbounded)),
                         other_set: other,
                     }
                 }
+                _ if other.len() <= self.len() / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF => {
+                    let mut other_iter = other.range(self_min..=self_max);
+                    let other_next = other_iter.next();
+                    DifferenceInner::Splice {
+                        other_iter,
+                        other_next,
+                        self_set: self,
+                        self_segment: match other_next {
+                            Some(other_next) => self.range(..other_next),
+                            None => self.range(..),
+                        },
+                    }
+                }
                 _ => DifferenceInner::Stitch {
-                    self_iter: self.iter(),
-                    other_iter: other.iter().peekable(),
+                    self_prefix: self.range(..other_min),
+                    self_iter: self.range(other_min..=other_max),
+                    self_suffix: self.range((Excluded(other_max), Unbounded)),
+                    other_iter: other.range(self_min..=self_max).peekable(),
                 },
             },
-        }
+            self,
+            other,
+        )
     }
 
     /// Visits the values representing the symmetric difference,
@@ -443,7 +893,13 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
                                     -> SymmetricDifference<'a, T> {
     */
     fn symmetric_difference<'a>(&'a self, other: &'a BTreeSet<T>) -> SymmetricDifference<'a, T> {
-        SymmetricDifference(MergeIterInner::new(self.iter(), other.iter()))
+        SymmetricDifference {
+            merge: MergeInner::new(self, other),
+            lens: MergeLens {
+                a_len: self.len(),
+                b_len: other.len(),
+            },
+        }
     }
 
     /*
@@ -476,42 +932,41 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
         {
             (self_min, self_max)
         } else {
-            return Intersection {
-                inner: IntersectionInner::Answer(None),
-            };
+            return Intersection::of_sets(IntersectionInner::Answer(None), self, other);
         };
         let (other_min, other_max) = if let (Some(other_min), Some(other_max)) =
             (other.iter().next(), other.iter().next_back())
         {
             (other_min, other_max)
         } else {
-            return Intersection {
-                inner: IntersectionInner::Answer(None),
-            };
+            return Intersection::of_sets(IntersectionInner::Answer(None), self, other);
         };
-        Intersection {
-            inner: match (self_min.cmp(other_max), self_max.cmp(other_min)) {
+        Intersection::of_sets(
+            match (self_min.cmp(other_max), self_max.cmp(other_min)) {
                 (Greater, _) | (_, Less) => IntersectionInner::Answer(None),
                 (Equal, _) => IntersectionInner::Answer(Some(self_min)),
                 (_, Equal) => IntersectionInner::Answer(Some(self_max)),
                 _ if self.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF => {
                     IntersectionInner::Search {
-                        small_iter: self.iter(),
+                        small_iter: self.range(max(self_min, other_min)..=min(self_max, other_max)),
                         large_set: other,
                     }
                 }
                 _ if other.len() <= self.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF => {
                     IntersectionInner::Search {
-                        small_iter: other.iter(),
+                        small_iter: other
+                            .range(max(self_min, other_min)..=min(self_max, other_max)),
                         large_set: self,
                     }
                 }
                 _ => IntersectionInner::Stitch {
-                    a: self.iter(),
-                    b: other.iter(),
+                    a: self.range(max(self_min, other_min)..=min(self_max, other_max)),
+                    b: other.range(max(self_min, other_min)..=min(self_max, other_max)),
                 },
             },
-        }
+            self,
+            other,
+        )
     }
 
     /*
@@ -537,7 +992,13 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
     pub fn union<'a>(&'a self, other: &'a BTreeSet<T>) -> Union<'a, T> {
     */
     fn union<'a>(&'a self, other: &'a BTreeSet<T>) -> Union<'a, T> {
-        Union(MergeIterInner::new(self.iter(), other.iter()))
+        Union {
+            merge: MergeInner::new(self, other),
+            lens: MergeLens {
+                a_len: self.len(),
+                b_len: other.len(),
+            },
+        }
     }
 
     /*
@@ -690,9 +1151,7 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
             }
         } else {
             // Self is not much smaller than other set.
-            let mut other_iter = other.iter();
-            other_iter.next();
-            other_iter.next_back();
+            let mut other_iter = other.range(self_min..=self_max);
             let mut self_next = self_iter.next();
             while let Some(self1) = self_next {
                 match other_iter.next().map_or(Less, |other1| self1.cmp(other1)) {
@@ -705,6 +1164,182 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
         true
     }
 
+    /*
+    /// Retains only the values that are also in `other`,
+    /// i.e., turns `self` into the intersection.
+    ///
+    /// Prunes `self` in place if it is much smaller than `other`.
+    /// Otherwise, moves the values that remain into a newly built set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut a = BTreeSet::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    ///
+    /// let mut b = BTreeSet::new();
+    /// b.insert(2);
+    /// b.insert(3);
+    ///
+    /// a.intersect_with(&b);
+    /// assert_eq!(a.into_iter().collect::<Vec<_>>(), [2]);
+    /// ```
+    #[unstable(feature = "btree_set_with", issue = "none")]
+    pub fn intersect_with(&mut self, other: &BTreeSet<T>) {
+    */
+    fn intersect_with(&mut self, other: &BTreeSet<T>) {
+        let (other_min, other_max) = if let (Some(other_min), Some(other_max)) =
+            (other.iter().next(), other.iter().next_back())
+        {
+            (other_min, other_max)
+        } else {
+            self.clear();
+            return;
+        };
+        // Prune the ends of self outside other's range.
+        *self = self.split_off(other_min);
+        let mut self_suffix = self.split_off(other_max);
+        if let Some(self_max) = self_suffix.take(other_max) {
+            self.insert(self_max);
+        }
+        if self.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            // Self is much smaller, look up its elements in the other set.
+            self.retain(|elt| other.contains(elt));
+        } else if other.len() <= self.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            // Other is much smaller, pick its elements out of self.
+            let mut kept = BTreeSet::new();
+            for elt in other {
+                if let Some(elt) = self.take(elt) {
+                    kept.insert(elt);
+                }
+            }
+            *self = kept;
+        } else {
+            // Similar sizes, rebuild self from both sets iterated jointly.
+            let mut other_iter = other.iter().peekable();
+            *self = mem::take(self)
+                .into_iter()
+                .filter(|elt| {
+                    while other_iter.next_if(|other_elt| *other_elt < elt).is_some() {}
+                    other_iter.peek() == Some(&elt)
+                })
+                .collect();
+        }
+    }
+
+    /*
+    /// Removes the values that are in `other`,
+    /// i.e., turns `self` into the difference, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut a = BTreeSet::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    ///
+    /// let mut b = BTreeSet::new();
+    /// b.insert(2);
+    /// b.insert(3);
+    ///
+    /// a.difference_with(&b);
+    /// assert_eq!(a.into_iter().collect::<Vec<_>>(), [1]);
+    /// ```
+    #[unstable(feature = "btree_set_with", issue = "none")]
+    pub fn difference_with(&mut self, other: &BTreeSet<T>) {
+    */
+    fn difference_with(&mut self, other: &BTreeSet<T>) {
+        let (self_min, self_max) = if let (Some(self_min), Some(self_max)) =
+            (self.iter().next(), self.iter().next_back())
+        {
+            (self_min, self_max)
+        } else {
+            return;
+        };
+        let (other_min, other_max) = if let (Some(other_min), Some(other_max)) =
+            (other.iter().next(), other.iter().next_back())
+        {
+            (other_min, other_max)
+        } else {
+            return;
+        };
+        if self_max < other_min || other_max < self_min {
+            return;
+        }
+        if other.len() <= self.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            // Other is much smaller, search and remove its elements.
+            let other_iter = other.range(self_min..=self_max);
+            for elt in other_iter {
+                self.remove(elt);
+            }
+        } else if self.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            // Self is much smaller, look up its elements in the other set.
+            self.retain(|elt| !other.contains(elt));
+        } else {
+            // Similar sizes, iterate both sets jointly.
+            let mut other_iter = other.range(self_min..=self_max).peekable();
+            self.retain(|elt| {
+                while other_iter.next_if(|other_elt| *other_elt < elt).is_some() {}
+                other_iter.peek() != Some(&elt)
+            });
+        }
+    }
+
+    /*
+    /// Moves all values from `other` into `self`,
+    /// i.e., turns `self` into the union.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut a = BTreeSet::new();
+    /// a.insert(1);
+    ///
+    /// let mut b = BTreeSet::new();
+    /// b.insert(2);
+    ///
+    /// a.union_with(b);
+    /// assert_eq!(a.into_iter().collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    #[unstable(feature = "btree_set_with", issue = "none")]
+    pub fn union_with(&mut self, other: BTreeSet<T>) {
+    */
+    fn union_with(&mut self, mut other: BTreeSet<T>) {
+        let disjoint = match (
+            self.iter().next(),
+            self.iter().next_back(),
+            other.iter().next(),
+            other.iter().next_back(),
+        ) {
+            (Some(self_min), Some(self_max), Some(other_min), Some(other_max)) => {
+                self_max < other_min || other_max < self_min
+            }
+            _ => true,
+        };
+        if disjoint {
+            // No element of one set equals one of the other,
+            // so appending amounts to a concatenation.
+            self.append(&mut other);
+        } else if self.len() < other.len() {
+            // Insert self's elements into the larger set instead,
+            // replacing the equal elements of other.
+            mem::swap(self, &mut other);
+            for elt in other {
+                self.replace(elt);
+            }
+        } else {
+            // Insert other's elements, which leaves equal elements of self.
+            self.extend(other);
+        }
+    }
+
     /*
     /// Returns `true` if the set is a superset of another,
     /// i.e., `self` contains at least all the values in `other`.
@@ -1224,34 +1859,84 @@ impl<T> Clone for Difference<'_, T> {
         Difference {
             inner: match &self.inner {
                 DifferenceInner::Stitch {
+                    self_prefix,
                     self_iter,
+                    self_suffix,
                     other_iter,
                 } => DifferenceInner::Stitch {
+                    self_prefix: self_prefix.clone(),
                     self_iter: self_iter.clone(),
+                    self_suffix: self_suffix.clone(),
                     other_iter: other_iter.clone(),
                 },
                 DifferenceInner::Search {
+                    self_prefix,
                     self_iter,
+                    self_suffix,
                     other_set,
                 } => DifferenceInner::Search {
+                    self_prefix: self_prefix.clone(),
                     self_iter: self_iter.clone(),
+                    self_suffix: self_suffix.clone(),
                     other_set,
                 },
+                DifferenceInner::Splice {
+                    other_iter,
+                    other_next,
+                    self_set,
+                    self_segment,
+                } => DifferenceInner::Splice {
+                    other_iter: other_iter.clone(),
+                    other_next: *other_next,
+                    self_set,
+                    self_segment: self_segment.clone(),
+                },
                 DifferenceInner::Iterate(iter) => DifferenceInner::Iterate(iter.clone()),
             },
+            lens: self.lens,
         }
     }
 }
-impl<'a, T: Ord> Iterator for Difference<'a, T> {
-    type Item = &'a T;
+impl<'a, T: Ord> Difference<'a, T> {
+    /// Starts a difference of whole sets, whose lengths bound its own.
+    fn of_sets(
+        inner: DifferenceInner<'a, T>,
+        self_set: &'a BTreeSet<T>,
+        other_set: &'a BTreeSet<T>,
+    ) -> Self {
+        let lens = match &inner {
+            DifferenceInner::Iterate(iter) => MergeLens {
+                a_len: iter.len(),
+                b_len: 0,
+            },
+            _ => MergeLens {
+                a_len: self_set.len(),
+                b_len: other_set.len(),
+            },
+        };
+        Difference { inner, lens }
+    }
+}
 
-    fn next(&mut self) -> Option<&'a T> {
-        match &mut self.inner {
+impl<'a, T: Ord> DifferenceInner<'a, T> {
+    /// Core of Difference::next, counting the elements it takes from self
+    /// as side a, and those it takes from other as side b.
+    fn next(&mut self, lens: &mut MergeLens) -> Option<&'a T> {
+        match self {
             DifferenceInner::Stitch {
+                self_prefix,
                 self_iter,
+                self_suffix,
                 other_iter,
+                ..
             } => {
-                let mut self_next = self_iter.next()?;
+                if let Some(self_next) = lens.took_a(self_prefix.next()) {
+                    return Some(self_next);
+                }
+                let mut self_next = match lens.took_a(self_iter.next()) {
+                    Some(self_next) => self_next,
+                    None => return lens.took_a(self_suffix.next()),
+                };
                 loop {
                     match other_iter
                         .peek()
@@ -1259,41 +1944,134 @@ impl<'a, T: Ord> Iterator for Difference<'a, T> {
                     {
                         Less => return Some(self_next),
                         Equal => {
-                            self_next = self_iter.next()?;
-                            other_iter.next();
+                            lens.took_b(other_iter.next());
+                            self_next = match lens.took_a(self_iter.next()) {
+                                Some(self_next) => self_next,
+                                None => return lens.took_a(self_suffix.next()),
+                            };
                         }
                         Greater => {
-                            other_iter.next();
+                            lens.took_b(other_iter.next());
                         }
                     }
                 }
             }
             DifferenceInner::Search {
+                self_prefix,
                 self_iter,
+                self_suffix,
                 other_set,
+                ..
+            } => {
+                if let Some(self_next) = lens.took_a(self_prefix.next()) {
+                    return Some(self_next);
+                }
+                while let Some(self_next) = lens.took_a(self_iter.next()) {
+                    if !other_set.contains(self_next) {
+                        return Some(self_next);
+                    }
+                }
+                lens.took_a(self_suffix.next())
+            }
+            DifferenceInner::Splice {
+                other_iter,
+                other_next,
+                self_set,
+                self_segment,
+                ..
             } => loop {
-                let self_next = self_iter.next()?;
-                if !other_set.contains(&self_next) {
+                if let Some(self_next) = lens.took_a(self_segment.next()) {
                     return Some(self_next);
                 }
+                // Leaves other uncounted: the segments skip the elements of
+                // self equal to those of other without counting them either,
+                // but there are never more of them than of other.
+                let lower = (*other_next)?;
+                *other_next = other_iter.next();
+                *self_segment = match *other_next {
+                    Some(upper) => self_set.range((Excluded(lower), Excluded(upper))),
+                    None => self_set.range((Excluded(lower), Unbounded)),
+                };
             },
-            DifferenceInner::Iterate(iter) => iter.next(),
+            DifferenceInner::Iterate(iter) => lens.took_a(iter.next()),
         }
     }
+}
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let (self_len, other_len) = match &self.inner {
+impl<'a, T: Ord> Iterator for Difference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next(&mut self.lens)
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self.inner {
             DifferenceInner::Stitch {
+                self_prefix,
                 self_iter,
-                other_iter,
-            } => (self_iter.len(), other_iter.len()),
+                self_suffix,
+                mut other_iter,
+                ..
+            } => {
+                let acc = self_prefix.fold(init, &mut f);
+                let acc = self_iter.fold(acc, |acc, self_next| loop {
+                    match other_iter
+                        .peek()
+                        .map_or(Less, |other_next| self_next.cmp(other_next))
+                    {
+                        Less => return f(acc, self_next),
+                        Equal => {
+                            other_iter.next();
+                            return acc;
+                        }
+                        Greater => {
+                            other_iter.next();
+                        }
+                    }
+                });
+                self_suffix.fold(acc, f)
+            }
             DifferenceInner::Search {
+                self_prefix,
                 self_iter,
+                self_suffix,
                 other_set,
-            } => (self_iter.len(), other_set.len()),
-            DifferenceInner::Iterate(iter) => (iter.len(), 0),
-        };
-        (self_len.saturating_sub(other_len), Some(self_len))
+                ..
+            } => {
+                let acc = self_prefix.fold(init, &mut f);
+                let acc = self_iter
+                    .filter(|self_next| !other_set.contains(self_next))
+                    .fold(acc, &mut f);
+                self_suffix.fold(acc, f)
+            }
+            DifferenceInner::Splice {
+                mut other_iter,
+                mut other_next,
+                self_set,
+                self_segment,
+                ..
+            } => {
+                let mut acc = self_segment.fold(init, &mut f);
+                while let Some(lower) = other_next {
+                    other_next = other_iter.next();
+                    let self_segment = match other_next {
+                        Some(upper) => self_set.range((Excluded(lower), Excluded(upper))),
+                        None => self_set.range((Excluded(lower), Unbounded)),
+                    };
+                    acc = self_segment.fold(acc, &mut f);
+                }
+                acc
+            }
+            DifferenceInner::Iterate(iter) => iter.fold(init, f),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lens.difference_hint()
     }
 }
 
@@ -1301,7 +2079,10 @@ impl<T: Ord> FusedIterator for Difference<'_, T> {}
 
 impl<T> Clone for SymmetricDifference<'_, T> {
     fn clone(&self) -> Self {
-        SymmetricDifference(self.0.clone())
+        SymmetricDifference {
+            merge: self.merge.clone(),
+            lens: self.lens,
+        }
     }
 }
 impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
@@ -1309,19 +2090,27 @@ impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
 
     fn next(&mut self) -> Option<&'a T> {
         loop {
-            let (a_next, b_next) = self.0.nexts();
+            let nexts = self.merge.nexts();
+            self.lens.took(&nexts);
+            let (a_next, b_next) = nexts;
             if a_next.and(b_next).is_none() {
                 return a_next.or(b_next);
             }
         }
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.merge.fold(init, |acc, nexts| match nexts {
+            (Some(next), None) | (None, Some(next)) => f(acc, next),
+            _ => acc,
+        })
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (a_len, b_len) = self.0.lens();
-        // No checked_add, because even if a and b refer to the same set,
-        // and T is an empty type, the storage overhead of sets limits
-        // the number of elements to less than half the range of usize.
-        (0, Some(a_len + b_len))
+        self.lens.symmdiff_hint()
     }
 }
 
@@ -1344,21 +2133,44 @@ impl<T> Clone for Intersection<'_, T> {
                 },
                 IntersectionInner::Answer(answer) => IntersectionInner::Answer(*answer),
             },
+            lens: self.lens,
         }
     }
 }
-impl<'a, T: Ord> Iterator for Intersection<'a, T> {
-    type Item = &'a T;
+impl<'a, T: Ord> Intersection<'a, T> {
+    /// Starts an intersection of whole sets, whose lengths bound its own.
+    fn of_sets(
+        inner: IntersectionInner<'a, T>,
+        self_set: &'a BTreeSet<T>,
+        other_set: &'a BTreeSet<T>,
+    ) -> Self {
+        let lens = match &inner {
+            // Side a being the small set.
+            IntersectionInner::Search { .. } => MergeLens {
+                a_len: min(self_set.len(), other_set.len()),
+                b_len: max(self_set.len(), other_set.len()),
+            },
+            _ => MergeLens {
+                a_len: self_set.len(),
+                b_len: other_set.len(),
+            },
+        };
+        Intersection { inner, lens }
+    }
+}
 
-    fn next(&mut self) -> Option<&'a T> {
-        match &mut self.inner {
-            IntersectionInner::Stitch { a, b } => {
-                let mut a_next = a.next()?;
-                let mut b_next = b.next()?;
+impl<'a, T: Ord> IntersectionInner<'a, T> {
+    /// Core of Intersection::next, counting the elements it takes
+    /// from either side, the small set being side a when searching.
+    fn next(&mut self, lens: &mut MergeLens) -> Option<&'a T> {
+        match self {
+            IntersectionInner::Stitch { a, b, .. } => {
+                let mut a_next = lens.took_a(a.next())?;
+                let mut b_next = lens.took_b(b.next())?;
                 loop {
                     match a_next.cmp(b_next) {
-                        Less => a_next = a.next()?,
-                        Greater => b_next = b.next()?,
+                        Less => a_next = lens.took_a(a.next())?,
+                        Greater => b_next = lens.took_b(b.next())?,
                         Equal => return Some(a_next),
                     }
                 }
@@ -1366,22 +2178,75 @@ impl<'a, T: Ord> Iterator for Intersection<'a, T> {
             IntersectionInner::Search {
                 small_iter,
                 large_set,
+                ..
             } => loop {
-                let small_next = small_iter.next()?;
-                if large_set.contains(&small_next) {
+                let small_next = lens.took_a(small_iter.next())?;
+                if large_set.contains(small_next) {
                     return Some(small_next);
                 }
             },
             IntersectionInner::Answer(answer) => answer.take(),
         }
     }
+}
+
+impl<'a, T: Ord> Iterator for Intersection<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next(&mut self.lens)
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self.inner {
+            IntersectionInner::Stitch { mut a, mut b, .. } => {
+                let mut acc = init;
+                let (mut a_next, mut b_next) = match (a.next(), b.next()) {
+                    (Some(a_next), Some(b_next)) => (a_next, b_next),
+                    _ => return acc,
+                };
+                loop {
+                    match a_next.cmp(b_next) {
+                        Less => match a.next() {
+                            Some(next) => a_next = next,
+                            None => return acc,
+                        },
+                        Greater => match b.next() {
+                            Some(next) => b_next = next,
+                            None => return acc,
+                        },
+                        Equal => {
+                            acc = f(acc, a_next);
+                            match (a.next(), b.next()) {
+                                (Some(a1), Some(b1)) => {
+                                    a_next = a1;
+                                    b_next = b1;
+                                }
+                                _ => return acc,
+                            }
+                        }
+                    }
+                }
+            }
+            IntersectionInner::Search {
+                small_iter,
+                large_set,
+                ..
+            } => small_iter
+                .filter(|small_next| large_set.contains(small_next))
+                .fold(init, f),
+            IntersectionInner::Answer(answer) => answer.into_iter().fold(init, f),
+        }
+    }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        match &self.inner {
-            IntersectionInner::Stitch { a, b } => (0, Some(min(a.len(), b.len()))),
-            IntersectionInner::Search { small_iter, .. } => (0, Some(small_iter.len())),
+        match self.inner {
             IntersectionInner::Answer(None) => (0, Some(0)),
             IntersectionInner::Answer(Some(_)) => (1, Some(1)),
+            _ => self.lens.intersection_hint(),
         }
     }
 }
@@ -1390,21 +2255,34 @@ impl<T: Ord> FusedIterator for Intersection<'_, T> {}
 
 impl<T> Clone for Union<'_, T> {
     fn clone(&self) -> Self {
-        Union(self.0.clone())
+        Union {
+            merge: self.merge.clone(),
+            lens: self.lens,
+        }
     }
 }
 impl<'a, T: Ord> Iterator for Union<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
-        let (a_next, b_next) = self.0.nexts();
+        let nexts = self.merge.nexts();
+        self.lens.took(&nexts);
+        let (a_next, b_next) = nexts;
         a_next.or(b_next)
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.merge.fold(init, |acc, nexts| match nexts {
+            (Some(next), _) | (None, Some(next)) => f(acc, next),
+            (None, None) => acc,
+        })
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (a_len, b_len) = self.0.lens();
-        // No checked_add - see SymmetricDifference::size_hint.
-        (max(a_len, b_len), Some(a_len + b_len))
+        self.lens.union_hint()
     }
 }
 
@@ -1414,10 +2292,40 @@ pub fn is_subset<'a, T: Ord>(selve: &'a BTreeSet<T>, other: &'a BTreeSet<T>) ->
     (selve as &dyn JustToIndentAsMuch<T>).is_subset(other)
 }
 
+pub fn intersect_with<T: Ord>(selve: &mut BTreeSet<T>, other: &BTreeSet<T>) {
+    (selve as &mut dyn JustToIndentAsMuch<T>).intersect_with(other)
+}
+
+pub fn difference_with<T: Ord>(selve: &mut BTreeSet<T>, other: &BTreeSet<T>) {
+    (selve as &mut dyn JustToIndentAsMuch<T>).difference_with(other)
+}
+
+pub fn union_with<T: Ord>(selve: &mut BTreeSet<T>, other: BTreeSet<T>) {
+    (selve as &mut dyn JustToIndentAsMuch<T>).union_with(other)
+}
+
 pub fn difference<'a, T: Ord>(selve: &'a BTreeSet<T>, other: &'a BTreeSet<T>) -> Difference<'a, T> {
     (selve as &dyn JustToIndentAsMuch<T>).difference(other)
 }
 
+pub fn difference_splice<'a, T: Ord>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<T>,
+) -> Difference<'a, T> {
+    let mut other_iter = other.range(..);
+    let other_next = other_iter.next();
+    let inner = DifferenceInner::Splice {
+        other_iter,
+        other_next,
+        self_set: selve,
+        self_segment: match other_next {
+            Some(other_next) => selve.range(..other_next),
+            None => selve.range(..),
+        },
+    };
+    Difference::of_sets(inner, selve, other)
+}
+
 pub fn intersection<'a, T: Ord>(
     selve: &'a BTreeSet<T>,
     other: &'a BTreeSet<T>,
@@ -1430,24 +2338,22 @@ pub fn intersection_search<'a, T: Ord>(
     large: &'a BTreeSet<T>,
 ) -> Intersection<'a, T> {
     assert!(small.len() <= large.len());
-    Intersection {
-        inner: IntersectionInner::Search {
-            small_iter: small.iter(),
-            large_set: &large,
-        },
-    }
+    let inner = IntersectionInner::Search {
+        small_iter: small.range(..),
+        large_set: large,
+    };
+    Intersection::of_sets(inner, small, large)
 }
 
 pub fn intersection_stitch<'a, T: Ord>(
     a: &'a BTreeSet<T>,
     b: &'a BTreeSet<T>,
 ) -> Intersection<'a, T> {
-    Intersection {
-        inner: IntersectionInner::Stitch {
-            a: a.iter(),
-            b: b.iter(),
-        },
-    }
+    let inner = IntersectionInner::Stitch {
+        a: a.range(..),
+        b: b.range(..),
+    };
+    Intersection::of_sets(inner, a, b)
 }
 
 pub fn symmdiff<'a, T: Ord>(
@@ -1457,6 +2363,150 @@ pub fn symmdiff<'a, T: Ord>(
     (selve as &dyn JustToIndentAsMuch<T>).symmetric_difference(other)
 }
 
+pub fn symmdiff_splice<'a, T: Ord>(
+    small: &'a BTreeSet<T>,
+    large: &'a BTreeSet<T>,
+) -> SymmetricDifference<'a, T> {
+    SymmetricDifference {
+        merge: MergeInner::Splice(MergeSpliceInner::new(small, MergeIterSide::A, large)),
+        lens: MergeLens {
+            a_len: small.len(),
+            b_len: large.len(),
+        },
+    }
+}
+
 pub fn union<'a, T: Ord>(selve: &'a BTreeSet<T>, other: &'a BTreeSet<T>) -> Union<'a, T> {
     (selve as &dyn JustToIndentAsMuch<T>).union(other)
 }
+
+pub fn union_splice<'a, T: Ord>(small: &'a BTreeSet<T>, large: &'a BTreeSet<T>) -> Union<'a, T> {
+    Union {
+        merge: MergeInner::Splice(MergeSpliceInner::new(small, MergeIterSide::A, large)),
+        lens: MergeLens {
+            a_len: small.len(),
+            b_len: large.len(),
+        },
+    }
+}
+
+// Owned results, as returned by the operators on `&BTreeSet`.
+
+pub fn bitand<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(intersection(selve, other).cloned())
+}
+
+pub fn bitor<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(union(selve, other).cloned())
+}
+
+pub fn bitxor<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(symmdiff(selve, other).cloned())
+}
+
+pub fn sub<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(difference(selve, other).cloned())
+}
+
+// Results consuming both sets, moving rather than cloning the elements.
+// They choose between strategies on the same sizes as the operations
+// borrowing the sets, and of elements present in both sets,
+// they keep the one in selve.
+
+pub fn into_intersection<T: Ord>(mut selve: BTreeSet<T>, other: BTreeSet<T>) -> BTreeSet<T> {
+    if selve.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+        // Selve is much smaller, look up its elements in the other set.
+        selve.retain(|elt| other.contains(elt));
+        selve
+    } else if other.len() <= selve.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+        // Other is much smaller, pick its elements out of selve.
+        other.iter().filter_map(|elt| selve.take(elt)).collect()
+    } else {
+        stitch_into(selve, other, false, true, false)
+    }
+}
+
+pub fn into_difference<T: Ord>(mut selve: BTreeSet<T>, other: BTreeSet<T>) -> BTreeSet<T> {
+    if selve.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+        // Selve is much smaller, look up its elements in the other set.
+        selve.retain(|elt| !other.contains(elt));
+        selve
+    } else if other.len() <= selve.len() / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF {
+        // Other is tiny, remove its elements from selve.
+        for elt in &other {
+            selve.remove(elt);
+        }
+        selve
+    } else {
+        stitch_into(selve, other, true, false, false)
+    }
+}
+
+pub fn into_union<T: Ord>(mut selve: BTreeSet<T>, mut other: BTreeSet<T>) -> BTreeSet<T> {
+    if selve.len() <= other.len() / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF {
+        // Selve is tiny, move its elements into the other set,
+        // replacing the equal elements there.
+        for elt in selve {
+            other.replace(elt);
+        }
+        other
+    } else if other.len() <= selve.len() / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF {
+        // Other is tiny, move its elements into selve,
+        // which leaves the equal elements of selve in place.
+        selve.extend(other);
+        selve
+    } else {
+        stitch_into(selve, other, true, true, true)
+    }
+}
+
+pub fn into_symmetric_difference<T: Ord>(selve: BTreeSet<T>, other: BTreeSet<T>) -> BTreeSet<T> {
+    let (small, mut large) = if selve.len() <= other.len() {
+        (selve, other)
+    } else {
+        (other, selve)
+    };
+    if small.len() <= large.len() / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF {
+        // One set is tiny, toggle its elements in the other.
+        for elt in small {
+            if !large.remove(&elt) {
+                large.insert(elt);
+            }
+        }
+        large
+    } else {
+        stitch_into(small, large, true, false, true)
+    }
+}
+
+/// Rebuilds a set from both sets iterated jointly, keeping the elements
+/// only in a, those in both (taking a's), and those only in b, as told.
+fn stitch_into<T: Ord>(
+    a: BTreeSet<T>,
+    b: BTreeSet<T>,
+    keep_a: bool,
+    keep_both: bool,
+    keep_b: bool,
+) -> BTreeSet<T> {
+    let mut a = a.into_iter().peekable();
+    let mut b = b.into_iter().peekable();
+    iter::from_fn(move || loop {
+        let next = match (a.peek(), b.peek()) {
+            (Some(a1), Some(b1)) => match a1.cmp(b1) {
+                Less => (a.next(), keep_a),
+                Greater => (b.next(), keep_b),
+                Equal => {
+                    b.next();
+                    (a.next(), keep_both)
+                }
+            },
+            (Some(_), None) => (a.next(), keep_a),
+            (None, Some(_)) => (b.next(), keep_b),
+            (None, None) => return None,
+        };
+        if let (Some(elt), true) = next {
+            return Some(elt);
+        }
+    })
+    .collect()
+}
@@ -6,6 +6,8 @@ use core::iter::{FusedIterator, Peekable};
 use std::collections::btree_set::Iter;
 use std::collections::BTreeSet;
 
+use crate::sorted_view::from_sorted;
+
 /*
 // This is pretty much entirely stolen from TreeSet, since BTreeMap has an identical interface
 // to TreeMap
@@ -171,6 +173,53 @@ where
         (a_next, b_next)
     }
 
+    /// Folds the pairs that nexts() would report, until both are exhausted,
+    /// keeping the next element of each side at hand instead of in `peeked`.
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (Option<I::Item>, Option<I::Item>)) -> B,
+    {
+        let MergeIterInner {
+            mut a,
+            mut b,
+            peeked,
+        } = self;
+        let (mut a_next, mut b_next) = match peeked {
+            Some(MergeIterPeeked::A(next)) => (Some(next), b.next()),
+            Some(MergeIterPeeked::B(next)) => (a.next(), Some(next)),
+            None => (a.next(), b.next()),
+        };
+        let mut acc = init;
+        loop {
+            match (a_next, b_next) {
+                (Some(a1), Some(b1)) => match a1.cmp(&b1) {
+                    Less => {
+                        acc = f(acc, (a_next, None));
+                        a_next = a.next();
+                    }
+                    Equal => {
+                        acc = f(acc, (a_next, b_next));
+                        a_next = a.next();
+                        b_next = b.next();
+                    }
+                    Greater => {
+                        acc = f(acc, (None, b_next));
+                        b_next = b.next();
+                    }
+                },
+                (Some(_), None) => {
+                    acc = f(acc, (a_next, None));
+                    return a.fold(acc, |acc, a1| f(acc, (Some(a1), None)));
+                }
+                (None, Some(_)) => {
+                    acc = f(acc, (None, b_next));
+                    return b.fold(acc, |acc, b1| f(acc, (None, Some(b1))));
+                }
+                (None, None) => return acc,
+            }
+        }
+    }
+
     fn lens(&self) -> (usize, usize) {
         match self.peeked {
             Some(MergeIterPeeked::A(_)) => (1 + self.a.len(), self.b.len()),
@@ -1273,7 +1322,7 @@ impl<'a, T: Ord> Iterator for Difference<'a, T> {
                 other_set,
             } => loop {
                 let self_next = self_iter.next()?;
-                if !other_set.contains(&self_next) {
+                if !other_set.contains(self_next) {
                     return Some(self_next);
                 }
             },
@@ -1281,6 +1330,39 @@ impl<'a, T: Ord> Iterator for Difference<'a, T> {
         }
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self.inner {
+            DifferenceInner::Stitch {
+                self_iter,
+                mut other_iter,
+            } => self_iter.fold(init, |acc, self_next| loop {
+                match other_iter
+                    .peek()
+                    .map_or(Less, |other_next| self_next.cmp(other_next))
+                {
+                    Less => return f(acc, self_next),
+                    Equal => {
+                        other_iter.next();
+                        return acc;
+                    }
+                    Greater => {
+                        other_iter.next();
+                    }
+                }
+            }),
+            DifferenceInner::Search {
+                self_iter,
+                other_set,
+            } => self_iter
+                .filter(|self_next| !other_set.contains(self_next))
+                .fold(init, f),
+            DifferenceInner::Iterate(iter) => iter.fold(init, f),
+        }
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (self_len, other_len) = match &self.inner {
             DifferenceInner::Stitch {
@@ -1316,12 +1398,23 @@ impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
         }
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.0.fold(init, |acc, nexts| match nexts {
+            (Some(next), None) | (None, Some(next)) => f(acc, next),
+            _ => acc,
+        })
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (a_len, b_len) = self.0.lens();
         // No checked_add, because even if a and b refer to the same set,
         // and T is an empty type, the storage overhead of sets limits
         // the number of elements to less than half the range of usize.
-        (0, Some(a_len + b_len))
+        // Each element present in both sets takes one from either side.
+        (a_len.abs_diff(b_len), Some(a_len + b_len))
     }
 }
 
@@ -1368,7 +1461,7 @@ impl<'a, T: Ord> Iterator for Intersection<'a, T> {
                 large_set,
             } => loop {
                 let small_next = small_iter.next()?;
-                if large_set.contains(&small_next) {
+                if large_set.contains(small_next) {
                     return Some(small_next);
                 }
             },
@@ -1376,6 +1469,50 @@ impl<'a, T: Ord> Iterator for Intersection<'a, T> {
         }
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self.inner {
+            IntersectionInner::Stitch { mut a, mut b } => {
+                let mut acc = init;
+                let (mut a_next, mut b_next) = match (a.next(), b.next()) {
+                    (Some(a_next), Some(b_next)) => (a_next, b_next),
+                    _ => return acc,
+                };
+                loop {
+                    match a_next.cmp(b_next) {
+                        Less => match a.next() {
+                            Some(next) => a_next = next,
+                            None => return acc,
+                        },
+                        Greater => match b.next() {
+                            Some(next) => b_next = next,
+                            None => return acc,
+                        },
+                        Equal => {
+                            acc = f(acc, a_next);
+                            match (a.next(), b.next()) {
+                                (Some(a1), Some(b1)) => {
+                                    a_next = a1;
+                                    b_next = b1;
+                                }
+                                _ => return acc,
+                            }
+                        }
+                    }
+                }
+            }
+            IntersectionInner::Search {
+                small_iter,
+                large_set,
+            } => small_iter
+                .filter(|small_next| large_set.contains(small_next))
+                .fold(init, f),
+            IntersectionInner::Answer(answer) => answer.into_iter().fold(init, f),
+        }
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         match &self.inner {
             IntersectionInner::Stitch { a, b } => (0, Some(min(a.len(), b.len()))),
@@ -1401,6 +1538,16 @@ impl<'a, T: Ord> Iterator for Union<'a, T> {
         a_next.or(b_next)
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.0.fold(init, |acc, nexts| match nexts {
+            (Some(next), _) | (None, Some(next)) => f(acc, next),
+            (None, None) => acc,
+        })
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (a_len, b_len) = self.0.lens();
         // No checked_add - see SymmetricDifference::size_hint.
@@ -1433,7 +1580,7 @@ pub fn intersection_search<'a, T: Ord>(
     Intersection {
         inner: IntersectionInner::Search {
             small_iter: small.iter(),
-            large_set: &large,
+            large_set: large,
         },
     }
 }
@@ -1460,3 +1607,21 @@ pub fn symmdiff<'a, T: Ord>(
 pub fn union<'a, T: Ord>(selve: &'a BTreeSet<T>, other: &'a BTreeSet<T>) -> Union<'a, T> {
     (selve as &dyn JustToIndentAsMuch<T>).union(other)
 }
+
+// Owned results, as returned by the operators on `&BTreeSet`.
+
+pub fn bitand<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(intersection(selve, other).cloned())
+}
+
+pub fn bitor<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(union(selve, other).cloned())
+}
+
+pub fn bitxor<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(symmdiff(selve, other).cloned())
+}
+
+pub fn sub<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(difference(selve, other).cloned())
+}
@@ -1,10 +1,16 @@
 // file comparable to rust/src/liballoc/collections/btree/set.rs
+use core::borrow::Borrow;
 use core::cmp::Ordering::{Equal, Greater, Less};
 use core::cmp::{max, min};
 use core::fmt::{self, Debug};
 use core::iter::FusedIterator;
-use std::collections::btree_set::Iter;
-use std::collections::BTreeSet;
+use core::marker::PhantomData;
+use core::ops::Bound::{self, Excluded, Included, Unbounded};
+use core::ops::RangeBounds;
+use std::collections::btree_set::{Iter, Range};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::sorted_view::{from_sorted, SortedView};
 
 /*
 // This is pretty much entirely stolen from TreeSet, since BTreeMap has an identical interface
@@ -125,8 +131,8 @@ pub struct Range<'a, T: 'a> {
 /// wrapped iterator, if any is left, and the remainder (`tail`)
 /// of the wrapped iterator is always one step beyond what the
 /// caller sees.
-/// This `struct` behaves as ExactSizeIterator and FusedIterator,
-/// but is not formally defined as such, because nobody needs it.
+/// This `struct` behaves as FusedIterator, but is not formally
+/// defined as such, because nobody needs it.
 #[derive(Clone, Debug)]
 struct Peeking<I>
 where
@@ -139,7 +145,7 @@ where
 
 impl<I> Peeking<I>
 where
-    I: ExactSizeIterator + FusedIterator,
+    I: FusedIterator,
     I::Item: Copy,
 {
     fn new(mut iter: I) -> Self {
@@ -152,70 +158,414 @@ where
         self.head = self.tail.next();
         next
     }
+}
+
+impl<I> Peeking<I>
+where
+    I: DoubleEndedIterator + FusedIterator,
+    I::Item: Copy,
+{
+    /// Takes the last item left, which is the head once the tail is exhausted.
+    fn next_back(&mut self) -> Option<I::Item> {
+        match self.tail.next_back() {
+            Some(back) => Some(back),
+            None => self.head.take(),
+        }
+    }
+}
+
+/// A pair of elements in the form that nexts() reports them.
+type Nexts<T> = (Option<T>, Option<T>);
 
-    fn len(&self) -> usize {
-        self.head.map_or(0, |_| 1 + self.tail.len())
+/// Walks two sorted sides backwards in step, and returns the greatest pair,
+/// in the form that nexts() reports pairs, that satisfies the predicate.
+fn rfind_merged<T, A, B, P>(mut a_back: A, mut b_back: B, mut pred: P) -> Option<Nexts<T>>
+where
+    T: Copy + Ord,
+    A: FnMut() -> Option<T>,
+    B: FnMut() -> Option<T>,
+    P: FnMut(&Nexts<T>) -> bool,
+{
+    let (mut a_last, mut b_last) = (a_back(), b_back());
+    loop {
+        let lasts = match (a_last, b_last) {
+            (None, None) => return None,
+            (Some(a1), Some(b1)) => match a1.cmp(&b1) {
+                Less => (None, b_last),
+                Equal => (a_last, b_last),
+                Greater => (a_last, None),
+            },
+            lasts => lasts,
+        };
+        if lasts.0.is_some() {
+            a_last = a_back();
+        }
+        if lasts.1.is_some() {
+            b_last = b_back();
+        }
+        if pred(&lasts) {
+            return Some(lasts);
+        }
     }
 }
 
 /// Core of SymmetricDifference and Union.
 /// More efficient than btree.map.MergeIter,
 /// and crucially for SymmetricDifference, nexts() reports on both sides.
+/// Elements of one side lower or greater than any on the other side,
+/// and the elements remaining once one side is exhausted,
+/// are streamed without comparisons.
 #[derive(Clone)]
 struct MergeIterInner<I>
 where
     I: Iterator,
     I::Item: Copy,
 {
+    prefix: I,
+    prefix_side: MergeIterSide,
     a: I,
     b: I,
-    peeked: Option<MergeIterPeeked<I>>,
+    suffix: I,
+    suffix_side: MergeIterSide,
+    state: MergeIterState<I>,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum MergeIterSide {
+    A,
+    B,
 }
 
 #[derive(Copy, Clone, Debug)]
-enum MergeIterPeeked<I: Iterator> {
-    A(I::Item),
-    B(I::Item),
+enum MergeIterState<I: Iterator> {
+    Prefix,           // stream the prefix
+    Compare,          // compare the next elements on both sides
+    PeekedA(I::Item), // compare with an element of a read ahead
+    PeekedB(I::Item), // compare with an element of b read ahead
+    StreamA,          // stream the remainder of a, then of b
+    StreamB,          // stream the remainder of b, then of a
+    Suffix,           // stream the suffix
+}
+
+impl MergeIterSide {
+    fn other(self) -> Self {
+        match self {
+            MergeIterSide::A => MergeIterSide::B,
+            MergeIterSide::B => MergeIterSide::A,
+        }
+    }
+
+    fn report<T>(self, next: Option<T>) -> (Option<T>, Option<T>) {
+        match self {
+            MergeIterSide::A => (next, None),
+            MergeIterSide::B => (None, next),
+        }
+    }
+}
+
+impl<'a, T: Ord> MergeIterInner<Range<'a, T>> {
+    fn new(a_set: &'a BTreeSet<T>, b_set: &'a BTreeSet<T>) -> Self {
+        Self::new_in(a_set, b_set, (Unbounded, Unbounded))
+    }
+
+    /// Merges the elements of both sets within the given bounds.
+    fn new_in(
+        a_set: &'a BTreeSet<T>,
+        b_set: &'a BTreeSet<T>,
+        bounds: (Bound<&T>, Bound<&T>),
+    ) -> Self {
+        let (a_range, b_range) = (a_set.range(bounds), b_set.range(bounds));
+        let (a_min, a_max, b_min, b_max) = match (
+            a_range.clone().next(),
+            a_range.clone().next_back(),
+            b_range.clone().next(),
+            b_range.clone().next_back(),
+        ) {
+            (Some(a_min), Some(a_max), Some(b_min), Some(b_max)) => (a_min, a_max, b_min, b_max),
+            _ => {
+                // One side is empty, so stream the other.
+                let empty = if a_range.clone().next().is_none() {
+                    a_range.clone()
+                } else {
+                    b_range.clone()
+                };
+                return MergeIterInner {
+                    prefix: empty.clone(),
+                    prefix_side: MergeIterSide::A,
+                    a: a_range,
+                    b: b_range,
+                    suffix: empty,
+                    suffix_side: MergeIterSide::A,
+                    state: MergeIterState::StreamA,
+                };
+            }
+        };
+        if a_max < b_min || b_max < a_min {
+            // Disjoint ranges, so stream one side after the other.
+            return MergeIterInner {
+                prefix: a_set.range(a_min..a_min),
+                prefix_side: MergeIterSide::A,
+                a: a_range,
+                b: b_range,
+                suffix: a_set.range(a_min..a_min),
+                suffix_side: MergeIterSide::A,
+                state: if a_max < b_min {
+                    MergeIterState::StreamA
+                } else {
+                    MergeIterState::StreamB
+                },
+            };
+        }
+        let (prefix, prefix_side, lo) = if a_min < b_min {
+            (a_set.range(a_min..b_min), MergeIterSide::A, b_min)
+        } else {
+            (b_set.range(b_min..a_min), MergeIterSide::B, a_min)
+        };
+        let (suffix, suffix_side, hi) = if a_max > b_max {
+            (
+                a_set.range((Excluded(b_max), Included(a_max))),
+                MergeIterSide::A,
+                b_max,
+            )
+        } else {
+            (
+                b_set.range((Excluded(a_max), Included(b_max))),
+                MergeIterSide::B,
+                a_max,
+            )
+        };
+        MergeIterInner {
+            prefix,
+            prefix_side,
+            a: a_set.range(lo..=hi),
+            b: b_set.range(lo..=hi),
+            suffix,
+            suffix_side,
+            state: MergeIterState::Prefix,
+        }
+    }
+
+    /// Skips the elements below the bound, given the sets merged.
+    fn advance_to(&mut self, a_set: &'a BTreeSet<T>, b_set: &'a BTreeSet<T>, bound: &T) {
+        let side_set = |side| match side {
+            MergeIterSide::A => a_set,
+            MergeIterSide::B => b_set,
+        };
+        advance_range(side_set(self.prefix_side), &mut self.prefix, bound);
+        advance_range(a_set, &mut self.a, bound);
+        advance_range(b_set, &mut self.b, bound);
+        advance_range(side_set(self.suffix_side), &mut self.suffix, bound);
+        if let MergeIterState::PeekedA(next) | MergeIterState::PeekedB(next) = self.state {
+            if next < bound {
+                self.state = MergeIterState::Compare;
+            }
+        }
+    }
 }
 
 impl<I> MergeIterInner<I>
 where
-    I: ExactSizeIterator + FusedIterator,
+    I: FusedIterator,
     I::Item: Copy + Ord,
 {
-    fn new(a: I, b: I) -> Self {
-        MergeIterInner { a, b, peeked: None }
-    }
-
     fn nexts(&mut self) -> (Option<I::Item>, Option<I::Item>) {
-        let mut a_next = match self.peeked {
-            Some(MergeIterPeeked::A(next)) => Some(next),
-            _ => self.a.next(),
-        };
-        let mut b_next = match self.peeked {
-            Some(MergeIterPeeked::B(next)) => Some(next),
-            _ => self.b.next(),
-        };
-        let ord = match (a_next, b_next) {
-            (None, None) => Equal,
-            (_, None) => Less,
-            (None, _) => Greater,
-            (Some(a1), Some(b1)) => a1.cmp(&b1),
+        let (mut a_next, mut b_next) = match self.state {
+            MergeIterState::Prefix => match self.prefix.next() {
+                Some(next) => return self.prefix_side.report(Some(next)),
+                None => (self.a.next(), self.b.next()),
+            },
+            MergeIterState::Compare => (self.a.next(), self.b.next()),
+            MergeIterState::PeekedA(a1) => (Some(a1), self.b.next()),
+            MergeIterState::PeekedB(b1) => (self.a.next(), Some(b1)),
+            MergeIterState::StreamA => match self.a.next() {
+                Some(a1) => return (Some(a1), None),
+                None => (None, self.b.next()),
+            },
+            MergeIterState::StreamB => match self.b.next() {
+                Some(b1) => return (None, Some(b1)),
+                None => (self.a.next(), None),
+            },
+            MergeIterState::Suffix => return self.suffix_side.report(self.suffix.next()),
         };
-        self.peeked = match ord {
-            Less => b_next.take().map(MergeIterPeeked::B),
-            Equal => None,
-            Greater => a_next.take().map(MergeIterPeeked::A),
+        self.state = match (a_next, b_next) {
+            (Some(a1), Some(b1)) => match a1.cmp(&b1) {
+                Less => {
+                    b_next = None;
+                    MergeIterState::PeekedB(b1)
+                }
+                Equal => MergeIterState::Compare,
+                Greater => {
+                    a_next = None;
+                    MergeIterState::PeekedA(a1)
+                }
+            },
+            (Some(_), None) => MergeIterState::StreamA,
+            (None, Some(_)) => MergeIterState::StreamB,
+            (None, None) => {
+                self.state = MergeIterState::Suffix;
+                return self.suffix_side.report(self.suffix.next());
+            }
         };
         (a_next, b_next)
     }
 
-    fn lens(&self) -> (usize, usize) {
-        match self.peeked {
-            Some(MergeIterPeeked::A(_)) => (1 + self.a.len(), self.b.len()),
-            Some(MergeIterPeeked::B(_)) => (self.a.len(), 1 + self.b.len()),
-            _ => (self.a.len(), self.b.len()),
+    /// Like nexts(), but skips over elements present on both sides.
+    /// Runs of those are skipped in a tight loop, bypassing the state machine.
+    fn nexts_unequal(&mut self) -> (Option<I::Item>, Option<I::Item>) {
+        loop {
+            if let MergeIterState::Compare = self.state {
+                loop {
+                    match (self.a.next(), self.b.next()) {
+                        (Some(a1), Some(b1)) => match a1.cmp(&b1) {
+                            Less => {
+                                self.state = MergeIterState::PeekedB(b1);
+                                return (Some(a1), None);
+                            }
+                            Equal => (),
+                            Greater => {
+                                self.state = MergeIterState::PeekedA(a1);
+                                return (None, Some(b1));
+                            }
+                        },
+                        (Some(a1), None) => {
+                            self.state = MergeIterState::StreamA;
+                            return (Some(a1), None);
+                        }
+                        (None, Some(b1)) => {
+                            self.state = MergeIterState::StreamB;
+                            return (None, Some(b1));
+                        }
+                        (None, None) => {
+                            self.state = MergeIterState::Suffix;
+                            return self.suffix_side.report(self.suffix.next());
+                        }
+                    }
+                }
+            }
+            match self.nexts() {
+                (Some(_), Some(_)) => (),
+                nexts => return nexts,
+            }
+        }
+    }
+
+    /// Folds the pairs that nexts() would report, until all parts are exhausted,
+    /// keeping the next element of each side at hand instead of in `state`.
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (Option<I::Item>, Option<I::Item>)) -> B,
+    {
+        let MergeIterInner {
+            prefix,
+            prefix_side,
+            mut a,
+            mut b,
+            suffix,
+            suffix_side,
+            state,
+        } = self;
+        let mut acc = init;
+        let (mut a_next, mut b_next) = match state {
+            MergeIterState::Prefix => {
+                acc = prefix.fold(acc, |acc, next| f(acc, prefix_side.report(Some(next))));
+                (a.next(), b.next())
+            }
+            MergeIterState::Compare => (a.next(), b.next()),
+            MergeIterState::PeekedA(a1) => (Some(a1), b.next()),
+            MergeIterState::PeekedB(b1) => (a.next(), Some(b1)),
+            MergeIterState::StreamA => match a.next() {
+                Some(a1) => (Some(a1), None),
+                None => (None, b.next()),
+            },
+            MergeIterState::StreamB => match b.next() {
+                Some(b1) => (None, Some(b1)),
+                None => (a.next(), None),
+            },
+            MergeIterState::Suffix => (None, None),
+        };
+        loop {
+            match (a_next, b_next) {
+                (Some(a1), Some(b1)) => match a1.cmp(&b1) {
+                    Less => {
+                        acc = f(acc, (a_next, None));
+                        a_next = a.next();
+                    }
+                    Equal => {
+                        acc = f(acc, (a_next, b_next));
+                        a_next = a.next();
+                        b_next = b.next();
+                    }
+                    Greater => {
+                        acc = f(acc, (None, b_next));
+                        b_next = b.next();
+                    }
+                },
+                // Stream the remainder of one side, then of the other,
+                // which only has elements left if the sides are disjoint.
+                (Some(_), None) => {
+                    acc = f(acc, (a_next, None));
+                    acc = a.fold(acc, |acc, a1| f(acc, (Some(a1), None)));
+                    acc = b.fold(acc, |acc, b1| f(acc, (None, Some(b1))));
+                    break;
+                }
+                (None, Some(_)) => {
+                    acc = f(acc, (None, b_next));
+                    acc = b.fold(acc, |acc, b1| f(acc, (None, Some(b1))));
+                    acc = a.fold(acc, |acc, a1| f(acc, (Some(a1), None)));
+                    break;
+                }
+                (None, None) => break,
+            }
+        }
+        suffix.fold(acc, |acc, next| f(acc, suffix_side.report(Some(next))))
+    }
+}
+
+impl<I> MergeIterInner<I>
+where
+    I: DoubleEndedIterator + FusedIterator,
+    I::Item: Copy + Ord,
+{
+    /// Returns the last pair that nexts() would report satisfying the
+    /// predicate, walking backwards through the suffix, both sides and
+    /// the prefix, and stopping at the first pair that does.
+    fn rfind<P>(self, mut pred: P) -> Option<Nexts<I::Item>>
+    where
+        P: FnMut(&Nexts<I::Item>) -> bool,
+    {
+        let MergeIterInner {
+            prefix,
+            prefix_side,
+            mut a,
+            mut b,
+            suffix,
+            suffix_side,
+            state,
+        } = self;
+        if let Some(lasts) = suffix
+            .rev()
+            .map(|last| suffix_side.report(Some(last)))
+            .find(&mut pred)
+        {
+            return Some(lasts);
+        }
+        // An element read ahead is the lowest left on its side.
+        let (mut a_front, mut b_front) = match state {
+            MergeIterState::PeekedA(a1) => (Some(a1), None),
+            MergeIterState::PeekedB(b1) => (None, Some(b1)),
+            _ => (None, None),
+        };
+        if let Some(lasts) = rfind_merged(
+            || a.next_back().or_else(|| a_front.take()),
+            || b.next_back().or_else(|| b_front.take()),
+            &mut pred,
+        ) {
+            return Some(lasts);
         }
+        prefix
+            .rev()
+            .map(|last| prefix_side.report(Some(last)))
+            .find(pred)
     }
 }
 
@@ -226,12 +576,350 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("MergeIterInner")
+            .field(&self.prefix)
             .field(&self.a)
             .field(&self.b)
+            .field(&self.suffix)
             .finish()
     }
 }
 
+/// Alternative core of SymmetricDifference and Union, for a small set
+/// against a large set. Walks the small set and streams the segments
+/// of the large set in between its elements, comparing only at the boundaries.
+#[derive(Debug)]
+struct MergeSpliceInner<'a, T: 'a> {
+    small_iter: Range<'a, T>,
+    small_side: MergeIterSide,
+    small_next: Option<&'a T>, // upper bound of the current segment
+    large_set: &'a BTreeSet<T>,
+    segment: Range<'a, T>,
+}
+
+impl<'a, T: Ord> MergeSpliceInner<'a, T> {
+    fn new(
+        small_set: &'a BTreeSet<T>,
+        small_side: MergeIterSide,
+        large_set: &'a BTreeSet<T>,
+    ) -> Self {
+        let mut small_iter = small_set.range(..);
+        let small_next = small_iter.next();
+        MergeSpliceInner {
+            small_iter,
+            small_side,
+            small_next,
+            large_set,
+            segment: match small_next {
+                Some(small_next) => large_set.range(..small_next),
+                None => large_set.range(..),
+            },
+        }
+    }
+
+    fn nexts(&mut self) -> (Option<&'a T>, Option<&'a T>) {
+        if let Some(large_next) = self.segment.next() {
+            return self.small_side.other().report(Some(large_next));
+        }
+        let small_next = match self.small_next {
+            Some(small_next) => small_next,
+            None => return (None, None),
+        };
+        self.small_next = self.small_iter.next();
+        self.segment = match self.small_next {
+            Some(upper) => self
+                .large_set
+                .range((Excluded(small_next), Excluded(upper))),
+            None => self.large_set.range((Excluded(small_next), Unbounded)),
+        };
+        let large_match = self.large_set.get(small_next);
+        match self.small_side {
+            MergeIterSide::A => (Some(small_next), large_match),
+            MergeIterSide::B => (large_match, Some(small_next)),
+        }
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (Option<&'a T>, Option<&'a T>)) -> B,
+    {
+        let MergeSpliceInner {
+            mut small_iter,
+            small_side,
+            mut small_next,
+            large_set,
+            segment,
+        } = self;
+        let large_side = small_side.other();
+        let mut acc = segment.fold(init, |acc, large_next| {
+            f(acc, large_side.report(Some(large_next)))
+        });
+        while let Some(lower) = small_next {
+            small_next = small_iter.next();
+            let segment = match small_next {
+                Some(upper) => large_set.range((Excluded(lower), Excluded(upper))),
+                None => large_set.range((Excluded(lower), Unbounded)),
+            };
+            let large_match = large_set.get(lower);
+            acc = f(
+                acc,
+                match small_side {
+                    MergeIterSide::A => (Some(lower), large_match),
+                    MergeIterSide::B => (large_match, Some(lower)),
+                },
+            );
+            acc = segment.fold(acc, |acc, large_next| {
+                f(acc, large_side.report(Some(large_next)))
+            });
+        }
+        acc
+    }
+
+    /// Skips the elements below the bound, given the small set.
+    fn advance_to(&mut self, small_set: &'a BTreeSet<T>, bound: &T) {
+        if self.small_next.is_some_and(|small_next| small_next < bound) {
+            advance_range(small_set, &mut self.small_iter, bound);
+            self.small_next = self.small_iter.next();
+            self.segment = match self.small_next {
+                Some(upper) => self.large_set.range((Included(bound), Excluded(upper))),
+                None => self.large_set.range((Included(bound), Unbounded)),
+            };
+        } else {
+            advance_range(self.large_set, &mut self.segment, bound);
+        }
+    }
+}
+
+impl<'a, T: Ord> MergeSpliceInner<'a, T> {
+    /// Like MergeIterInner::rfind.
+    fn rfind<P>(self, pred: P) -> Option<Nexts<&'a T>>
+    where
+        P: FnMut(&Nexts<&'a T>) -> bool,
+    {
+        let MergeSpliceInner {
+            mut small_iter,
+            small_side,
+            mut small_next,
+            large_set,
+            mut segment,
+        } = self;
+        // The large set's elements left are those in the current segment,
+        // and those from the small element bounding that segment onwards.
+        let mut large_rest = small_next.map(|small_next| large_set.range(small_next..));
+        let small_back = || small_iter.next_back().or_else(|| small_next.take());
+        let large_back = || {
+            large_rest
+                .as_mut()
+                .and_then(|large_rest| large_rest.next_back())
+                .or_else(|| segment.next_back())
+        };
+        match small_side {
+            MergeIterSide::A => rfind_merged(small_back, large_back, pred),
+            MergeIterSide::B => rfind_merged(large_back, small_back, pred),
+        }
+    }
+}
+
+impl<T> Clone for MergeSpliceInner<'_, T> {
+    fn clone(&self) -> Self {
+        MergeSpliceInner {
+            small_iter: self.small_iter.clone(),
+            small_side: self.small_side,
+            small_next: self.small_next,
+            large_set: self.large_set,
+            segment: self.segment.clone(),
+        }
+    }
+}
+
+/// Choice between the cores of SymmetricDifference and Union.
+#[derive(Debug)]
+enum MergeInner<'a, T: 'a> {
+    Stitch(MergeIterInner<Range<'a, T>>), // merge sets of similar size
+    Splice(MergeSpliceInner<'a, T>),      // splice a small set into a large set
+}
+
+impl<'a, T: Ord> MergeInner<'a, T> {
+    fn new(a_set: &'a BTreeSet<T>, b_set: &'a BTreeSet<T>) -> Self {
+        if a_set.len() <= b_set.len() / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF {
+            MergeInner::Splice(MergeSpliceInner::new(a_set, MergeIterSide::A, b_set))
+        } else if b_set.len() <= a_set.len() / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF {
+            MergeInner::Splice(MergeSpliceInner::new(b_set, MergeIterSide::B, a_set))
+        } else {
+            MergeInner::Stitch(MergeIterInner::new(a_set, b_set))
+        }
+    }
+
+    fn nexts(&mut self) -> (Option<&'a T>, Option<&'a T>) {
+        match self {
+            MergeInner::Stitch(merge) => merge.nexts(),
+            MergeInner::Splice(splice) => splice.nexts(),
+        }
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, (Option<&'a T>, Option<&'a T>)) -> B,
+    {
+        match self {
+            MergeInner::Stitch(merge) => merge.fold(init, f),
+            MergeInner::Splice(splice) => splice.fold(init, f),
+        }
+    }
+
+    fn rfind<P>(self, pred: P) -> Option<Nexts<&'a T>>
+    where
+        P: FnMut(&Nexts<&'a T>) -> bool,
+    {
+        match self {
+            MergeInner::Stitch(merge) => merge.rfind(pred),
+            MergeInner::Splice(splice) => splice.rfind(pred),
+        }
+    }
+
+    fn advance_to(&mut self, a_set: &'a BTreeSet<T>, b_set: &'a BTreeSet<T>, bound: &T) {
+        match self {
+            MergeInner::Stitch(merge) => merge.advance_to(a_set, b_set, bound),
+            MergeInner::Splice(splice) => match splice.small_side {
+                MergeIterSide::A => splice.advance_to(a_set, bound),
+                MergeIterSide::B => splice.advance_to(b_set, bound),
+            },
+        }
+    }
+
+    fn nexts_unequal(&mut self) -> (Option<&'a T>, Option<&'a T>) {
+        match self {
+            MergeInner::Stitch(merge) => merge.nexts_unequal(),
+            MergeInner::Splice(splice) => loop {
+                match splice.nexts() {
+                    (Some(_), Some(_)) => (),
+                    nexts => return nexts,
+                }
+            },
+        }
+    }
+}
+
+impl<T> Clone for MergeInner<'_, T> {
+    fn clone(&self) -> Self {
+        match self {
+            MergeInner::Stitch(merge) => MergeInner::Stitch(merge.clone()),
+            MergeInner::Splice(splice) => MergeInner::Splice(splice.clone()),
+        }
+    }
+}
+
+/// The number of elements left on either side of an operation, counting down
+/// as the operation takes elements from either side. Exact for an operation
+/// on whole sets, until it skips elements uncounted, and otherwise only
+/// upper bounds. Difference and Intersection don't take the elements of
+/// the large set they search in, so these remain upper bounds for those.
+#[derive(Clone, Copy, Debug)]
+struct MergeLens {
+    a_len: usize,
+    b_len: usize,
+    exact: bool,
+}
+
+impl MergeLens {
+    fn exact(a_len: usize, b_len: usize) -> Self {
+        MergeLens {
+            a_len,
+            b_len,
+            exact: true,
+        }
+    }
+
+    fn at_most(a_len: usize, b_len: usize) -> Self {
+        MergeLens {
+            a_len,
+            b_len,
+            exact: false,
+        }
+    }
+
+    /// Bounds ranges whose lengths range_lens estimated, out of sets of
+    /// the given lengths: the shorter one it either counted exactly,
+    /// or estimated by the length of its set.
+    fn of_ranges((a_len, b_len): (usize, usize), (a_set_len, b_set_len): (usize, usize)) -> Self {
+        MergeLens::at_most(
+            if a_len <= b_len { a_len } else { a_set_len },
+            if b_len <= a_len { b_len } else { b_set_len },
+        )
+    }
+
+    fn took<T>(&mut self, (a_next, b_next): &Nexts<T>) {
+        if a_next.is_some() {
+            self.a_len -= 1;
+        }
+        if b_next.is_some() {
+            self.b_len -= 1;
+        }
+    }
+
+    /// Counts the element taken from side a, if any, and passes it on.
+    fn took_a<T>(&mut self, a_next: Option<T>) -> Option<T> {
+        if a_next.is_some() {
+            self.a_len -= 1;
+        }
+        a_next
+    }
+
+    /// Counts the element taken from side b, if any, and passes it on.
+    fn took_b<T>(&mut self, b_next: Option<T>) -> Option<T> {
+        if b_next.is_some() {
+            self.b_len -= 1;
+        }
+        b_next
+    }
+
+    fn skipped(&mut self) {
+        self.exact = false;
+    }
+
+    fn max_len(&self) -> usize {
+        // No checked_add, because even if a and b refer to the same set,
+        // and T is an empty type, the storage overhead of sets limits
+        // the number of elements to less than half the range of usize.
+        self.a_len + self.b_len
+    }
+
+    /// Bounds the length of the difference of what's left.
+    fn difference_hint(&self) -> (usize, Option<usize>) {
+        let min_len = if self.exact {
+            self.a_len.saturating_sub(self.b_len)
+        } else {
+            0
+        };
+        (min_len, Some(self.a_len))
+    }
+
+    /// Bounds the length of the intersection of what's left.
+    fn intersection_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(min(self.a_len, self.b_len)))
+    }
+
+    /// Bounds the length of the union of what's left.
+    fn union_hint(&self) -> (usize, Option<usize>) {
+        let min_len = if self.exact {
+            max(self.a_len, self.b_len)
+        } else {
+            0
+        };
+        (min_len, Some(self.max_len()))
+    }
+
+    /// Bounds the length of the symmetric difference of what's left.
+    /// Skipping pairs of equal elements uncounted keeps the lower bound.
+    fn symmdiff_hint(&self) -> (usize, Option<usize>) {
+        let min_len = if self.exact {
+            self.a_len.abs_diff(self.b_len)
+        } else {
+            0
+        };
+        (min_len, Some(self.max_len()))
+    }
+}
+
 /// A lazy iterator producing elements in the difference of `BTreeSet`s.
 ///
 /// This `struct` is created by the [`difference`] method on [`BTreeSet`].
@@ -239,25 +927,44 @@ where
 ///
 /// [`BTreeSet`]: struct.BTreeSet.html
 /// [`difference`]: struct.BTreeSet.html#method.difference
-pub struct Difference<'a, T: 'a> {
-    inner: DifferenceInner<'a, T>,
+pub struct Difference<'a, T: 'a, U: 'a = T, Q: ?Sized = T> {
+    inner: DifferenceInner<'a, T, U>,
+    lens: MergeLens,
+    self_set: &'a BTreeSet<T>,
+    other_set: &'a BTreeSet<U>,
+    marker: PhantomData<fn(&Q)>, // the type in which elements are compared
 }
 #[derive(Debug)]
-enum DifferenceInner<'a, T: 'a> {
+enum DifferenceInner<'a, T: 'a, U: 'a> {
     Stitch {
-        // iterate all of self and some of other, spotting matches along the way
-        self_iter: Iter<'a, T>,
-        other_iter: Peeking<Iter<'a, T>>,
+        // stream self's elements outside other's range, and iterate the rest
+        // of self and the part of other within self's range jointly,
+        // spotting matches along the way
+        self_prefix: Range<'a, T>,
+        self_iter: Range<'a, T>,
+        self_suffix: Range<'a, T>,
+        other_iter: Peeking<Range<'a, U>>,
     },
     Search {
-        // iterate a small set, look up in the large set
-        self_iter: Iter<'a, T>,
-        other_set: &'a BTreeSet<T>,
+        // stream self's elements outside other's range, and iterate the rest
+        // of the small self, looking up in the large other set
+        self_prefix: Range<'a, T>,
+        self_iter: Range<'a, T>,
+        self_suffix: Range<'a, T>,
+        other_set: &'a BTreeSet<U>,
+    },
+    Splice {
+        // iterate the part of the small other set within self's range,
+        // streaming the segments of self in between its elements
+        other_iter: Range<'a, U>,
+        other_next: Option<&'a U>, // upper bound of the current segment
+        self_set: &'a BTreeSet<T>,
+        self_segment: Range<'a, T>,
     },
     Iterate(Iter<'a, T>), // simply stream self's elements
 }
 
-impl<T: fmt::Debug> fmt::Debug for Difference<'_, T> {
+impl<T: fmt::Debug, U: fmt::Debug, Q: ?Sized> fmt::Debug for Difference<'_, T, U, Q> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Difference").field(&self.inner).finish()
     }
@@ -270,11 +977,16 @@ impl<T: fmt::Debug> fmt::Debug for Difference<'_, T> {
 ///
 /// [`BTreeSet`]: struct.BTreeSet.html
 /// [`symmetric_difference`]: struct.BTreeSet.html#method.symmetric_difference
-pub struct SymmetricDifference<'a, T: 'a>(MergeIterInner<Iter<'a, T>>);
+pub struct SymmetricDifference<'a, T: 'a> {
+    merge: MergeInner<'a, T>,
+    lens: MergeLens,
+}
 
 impl<T: fmt::Debug> fmt::Debug for SymmetricDifference<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("SymmetricDifference").field(&self.0).finish()
+        f.debug_tuple("SymmetricDifference")
+            .field(&self.merge)
+            .finish()
     }
 }
 
@@ -285,30 +997,63 @@ impl<T: fmt::Debug> fmt::Debug for SymmetricDifference<'_, T> {
 ///
 /// [`BTreeSet`]: struct.BTreeSet.html
 /// [`intersection`]: struct.BTreeSet.html#method.intersection
-pub struct Intersection<'a, T: 'a> {
-    inner: IntersectionInner<'a, T>,
+pub struct Intersection<'a, T: 'a, U: 'a = T, Q: ?Sized = T> {
+    inner: IntersectionInner<'a, T, U>,
+    lens: MergeLens,
+    self_set: &'a BTreeSet<T>,
+    other_set: &'a BTreeSet<U>,
+    marker: PhantomData<fn(&Q)>, // the type in which elements are compared
 }
 #[derive(Debug)]
-enum IntersectionInner<'a, T: 'a> {
+enum IntersectionInner<'a, T: 'a, U: 'a> {
     Stitch {
-        // iterate similarly sized sets jointly, spotting matches along the way
-        a: Iter<'a, T>,
-        b: Iter<'a, T>,
+        // iterate the overlapping parts of similarly sized sets jointly,
+        // spotting matches along the way
+        a: Range<'a, T>,
+        b: Range<'a, U>,
     },
-    Search {
-        // iterate a small set, look up in the large set
-        small_iter: Iter<'a, T>,
+    SearchOther {
+        // iterate the part of the small self overlapping the large other set,
+        // look up in the other set
+        small_iter: Range<'a, T>,
+        large_set: &'a BTreeSet<U>,
+    },
+    SearchSelf {
+        // iterate the part of the small other set overlapping the large self,
+        // look up the matching elements of self
+        small_iter: Range<'a, U>,
         large_set: &'a BTreeSet<T>,
     },
-    Answer(Option<&'a T>), // return a specific value or emptiness
+    Answer(Option<(&'a T, &'a U)>), // return a specific match or emptiness
 }
 
-impl<T: fmt::Debug> fmt::Debug for Intersection<'_, T> {
+impl<T: fmt::Debug, U: fmt::Debug, Q: ?Sized> fmt::Debug for Intersection<'_, T, U, Q> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Intersection").field(&self.inner).finish()
     }
 }
 
+/// A lazy iterator producing the pairs of matching elements
+/// in the intersection of `BTreeSet`s.
+///
+/// This `struct` is created by the [`intersection_pairs`] function.
+/// See its documentation for more.
+///
+/// [`intersection_pairs`]: fn.intersection_pairs.html
+pub struct IntersectionPairs<'a, T: 'a, U: 'a = T, Q: ?Sized = T> {
+    inner: IntersectionInner<'a, T, U>,
+    lens: MergeLens,
+    marker: PhantomData<fn(&Q)>, // the type in which elements are compared
+}
+
+impl<T: fmt::Debug, U: fmt::Debug, Q: ?Sized> fmt::Debug for IntersectionPairs<'_, T, U, Q> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntersectionPairs")
+            .field(&self.inner)
+            .finish()
+    }
+}
+
 /// A lazy iterator producing elements in the union of `BTreeSet`s.
 ///
 /// This `struct` is created by the [`union`] method on [`BTreeSet`].
@@ -316,37 +1061,307 @@ impl<T: fmt::Debug> fmt::Debug for Intersection<'_, T> {
 ///
 /// [`BTreeSet`]: struct.BTreeSet.html
 /// [`union`]: struct.BTreeSet.html#method.union
-pub struct Union<'a, T: 'a>(MergeIterInner<Iter<'a, T>>);
+pub struct Union<'a, T: 'a> {
+    merge: MergeInner<'a, T>,
+    lens: MergeLens,
+    self_set: &'a BTreeSet<T>,
+    other_set: &'a BTreeSet<T>,
+}
 
 impl<T: fmt::Debug> fmt::Debug for Union<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("Union").field(&self.0).finish()
+        f.debug_tuple("Union").field(&self.merge).finish()
     }
 }
 
-// This constant is used by functions that compare two sets.
-// It estimates the relative size at which searching performs better
-// than iterating, based on the benchmarks in
-// https://github.com/ssomers/rust_bench_btreeset_intersection;
-// It's used to divide rather than multiply sizes, to rule out overflow,
-// and it's a power of two to make that division cheap.
-const ITER_PERFORMANCE_TIPPING_SIZE_DIFF: usize = 16;
+/// A lazy iterator producing the pairs of elements of two `BTreeSet`s
+/// whose keys match.
+///
+/// This `struct` is created by the [`intersection_by_key`] function.
+/// See its documentation for more.
+///
+/// [`intersection_by_key`]: fn.intersection_by_key.html
+#[derive(Clone)]
+pub struct IntersectionByKey<'a, A: 'a, B: 'a, K, FA, FB> {
+    a_iter: Iter<'a, A>,
+    a_head: Option<(&'a A, K)>, // the element of a being paired, and its key
+    b_run: Iter<'a, B>,         // b from the first element not keyed below a_head
+    b_pair: Iter<'a, B>,        // the rest of b_run to pair a_head with
+    key_a: FA,
+    key_b: FB,
+}
 
-/*
-impl<T: Ord> BTreeSet<T> {
-    /// Makes a new `BTreeSet` with a reasonable choice of B.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// # #![allow(unused_mut)]
-    /// use std::collections::BTreeSet;
-    ///
-    /// let mut set: BTreeSet<i32> = BTreeSet::new();
-    /// ```
-    #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn new() -> BTreeSet<T> {
-        BTreeSet { map: BTreeMap::new() }
+impl<A: Debug, B: Debug, K: Debug, FA, FB> Debug for IntersectionByKey<'_, A, B, K, FA, FB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntersectionByKey")
+            .field("a_iter", &self.a_iter)
+            .field("a_head", &self.a_head)
+            .field("b_run", &self.b_run)
+            .field("b_pair", &self.b_pair)
+            .finish()
+    }
+}
+
+/// A lazy iterator producing the elements of a `BTreeSet` whose key
+/// matches no element of another `BTreeSet`.
+///
+/// This `struct` is created by the [`difference_by_key`] function.
+/// See its documentation for more.
+///
+/// [`difference_by_key`]: fn.difference_by_key.html
+#[derive(Clone)]
+pub struct DifferenceByKey<'a, A: 'a, B: 'a, FA, FB> {
+    a_iter: Iter<'a, A>,
+    b_iter: Peeking<Iter<'a, B>>,
+    key_a: FA,
+    key_b: FB,
+}
+
+impl<A: Debug, B: Debug, FA, FB> Debug for DifferenceByKey<'_, A, B, FA, FB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DifferenceByKey")
+            .field("a_iter", &self.a_iter)
+            .field("b_iter", &self.b_iter)
+            .finish()
+    }
+}
+
+/// An element of either or both of two `BTreeSet`s,
+/// as produced by [`Classify`].
+///
+/// [`Classify`]: struct.Classify.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Merged<'a, T: 'a> {
+    Left(&'a T),
+    Both(&'a T, &'a T),
+    Right(&'a T),
+}
+
+/// A lazy iterator telling, for each element of two `BTreeSet`s,
+/// in which of them it is present.
+///
+/// This `struct` is created by the [`classify`] and [`classify_changes`]
+/// functions. See their documentation for more.
+///
+/// [`classify`]: fn.classify.html
+/// [`classify_changes`]: fn.classify_changes.html
+pub struct Classify<'a, T: 'a> {
+    merge: MergeInner<'a, T>,
+    skip_both: bool, // whether to leave out elements present in both sets
+    lens: MergeLens,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Classify<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Classify").field(&self.merge).finish()
+    }
+}
+
+/// A lazy iterator producing elements in the difference of sorted views.
+///
+/// This `struct` is created by the [`difference`] function
+/// on views other than two `BTreeSet`s.
+///
+/// [`difference`]: fn.difference.html
+pub struct ViewDifference<'a, T: 'a, A: SortedView<'a, T>, B: SortedView<'a, T>> {
+    inner: ViewDifferenceInner<'a, T, A, B>,
+}
+#[derive(Debug)]
+enum ViewDifferenceInner<'a, T: 'a, A: SortedView<'a, T>, B: SortedView<'a, T>> {
+    Stitch {
+        // iterate a and the part of b from a's minimum on jointly,
+        // spotting matches along the way
+        a_iter: A::Iter,
+        b_iter: Peeking<B::Iter>,
+    },
+    Search {
+        // iterate the small view a, look up in the large view b
+        a_iter: A::Iter,
+        b: B,
+    },
+}
+
+impl<'a, T: 'a, A: SortedView<'a, T>, B: SortedView<'a, T>> Debug for ViewDifference<'a, T, A, B>
+where
+    ViewDifferenceInner<'a, T, A, B>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ViewDifference").field(&self.inner).finish()
+    }
+}
+
+/// A lazy iterator producing elements in the intersection of sorted views.
+/// When searching the other view for the elements of a small view,
+/// it produces the elements of the small view.
+///
+/// This `struct` is created by the [`intersection`] function
+/// on views other than two `BTreeSet`s.
+///
+/// [`intersection`]: fn.intersection.html
+pub struct ViewIntersection<'a, T: 'a, A: SortedView<'a, T>, B: SortedView<'a, T>> {
+    inner: ViewIntersectionInner<'a, T, A, B>,
+}
+#[derive(Debug)]
+enum ViewIntersectionInner<'a, T: 'a, A: SortedView<'a, T>, B: SortedView<'a, T>> {
+    Stitch {
+        // iterate the overlapping parts of similarly sized views jointly,
+        // spotting matches along the way
+        a_iter: A::Iter,
+        b_iter: B::Iter,
+    },
+    SearchB {
+        // iterate the small view a, look up in the large view b
+        a_iter: A::Iter,
+        b: B,
+    },
+    SearchA {
+        // iterate the small view b, look up in the large view a
+        b_iter: B::Iter,
+        a: A,
+    },
+}
+
+impl<'a, T: 'a, A: SortedView<'a, T>, B: SortedView<'a, T>> Debug for ViewIntersection<'a, T, A, B>
+where
+    ViewIntersectionInner<'a, T, A, B>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ViewIntersection")
+            .field(&self.inner)
+            .finish()
+    }
+}
+
+/// A lazy iterator producing elements in the union of sorted views.
+///
+/// This `struct` is created by the [`union`] function
+/// on views other than two `BTreeSet`s.
+///
+/// [`union`]: fn.union.html
+pub struct ViewUnion<'a, T: 'a, A: SortedView<'a, T>, B: SortedView<'a, T>> {
+    a_iter: Peeking<A::Iter>,
+    b_iter: Peeking<B::Iter>,
+}
+
+impl<'a, T: 'a, A: SortedView<'a, T>, B: SortedView<'a, T>> Debug for ViewUnion<'a, T, A, B>
+where
+    A::Iter: Debug,
+    B::Iter: Debug,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ViewUnion")
+            .field(&self.a_iter)
+            .field(&self.b_iter)
+            .finish()
+    }
+}
+
+// This constant is used by functions that compare two sets.
+// It estimates the relative size at which searching performs better
+// than iterating, based on the benchmarks in
+// https://github.com/ssomers/rust_bench_btreeset_intersection;
+// It's used to divide rather than multiply sizes, to rule out overflow,
+// and it's a power of two to make that division cheap.
+const ITER_PERFORMANCE_TIPPING_SIZE_DIFF: usize = 16;
+
+// Likewise, the relative size at which streaming segments of the large set
+// in between the elements of the small set performs better than merging.
+// Each segment costs a few searches, and iterating a Range is slower than
+// iterating a whole set, so it only pays off for long segments. In the
+// uni_tip and sym_tip benchmarks, splicing wins from about 48 times the size,
+// except when the small set lies within a single run of the large set, and
+// in the dif_tip benchmarks, difference already wins from about 32 times.
+const SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF: usize = 64;
+
+/// Returns an empty range into a set.
+fn empty_range<'a, T, Q>(set: &'a BTreeSet<T>, value: &Q) -> Range<'a, T>
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    set.range::<Q, _>((Included(value), Excluded(value)))
+}
+
+/// Repositions a range into a set to start at the first element not below
+/// the bound, unless it already does, while keeping its upper end.
+fn advance_range<'a, T, Q>(set: &'a BTreeSet<T>, range: &mut Range<'a, T>, bound: &Q)
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    if let (Some(first), Some(last)) = (range.clone().next(), range.clone().next_back()) {
+        if first.borrow() < bound {
+            *range = if bound <= last.borrow() {
+                set.range::<Q, _>((Included(bound), Included(last.borrow())))
+            } else {
+                empty_range(set, bound)
+            };
+        }
+    }
+}
+
+/// Like advance_range, for a range read ahead.
+fn advance_peeking<'a, T, Q>(set: &'a BTreeSet<T>, peeking: &mut Peeking<Range<'a, T>>, bound: &Q)
+where
+    T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    if peeking.head.is_some_and(|head| head.borrow() < bound) {
+        advance_range(set, &mut peeking.tail, bound);
+        peeking.head = peeking.tail.next();
+    }
+}
+
+/// Estimates the lengths of two ranges, as far as needed to tell whether
+/// one is ITER_PERFORMANCE_TIPPING_SIZE_DIFF times as long as the other,
+/// without walking either range far. If one range ends within that many
+/// elements, counts it exactly, and the other one no further than that many
+/// times its length. Otherwise, both ranges are long enough for the lengths
+/// of their sets to serve as estimates, which are also upper bounds.
+fn range_lens<T, U>(
+    mut a: Range<'_, T>,
+    mut b: Range<'_, U>,
+    set_lens: (usize, usize),
+) -> (usize, usize) {
+    for len in 0..ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+        match (a.next(), b.next()) {
+            (Some(_), Some(_)) => {}
+            (Some(_), None) => {
+                let max_len = len * ITER_PERFORMANCE_TIPPING_SIZE_DIFF;
+                return (
+                    len + 1 + a.take(max_len.saturating_sub(len + 1)).count(),
+                    len,
+                );
+            }
+            (None, Some(_)) => {
+                let max_len = len * ITER_PERFORMANCE_TIPPING_SIZE_DIFF;
+                return (
+                    len,
+                    len + 1 + b.take(max_len.saturating_sub(len + 1)).count(),
+                );
+            }
+            (None, None) => return (len, len),
+        }
+    }
+    set_lens
+}
+
+/*
+impl<T: Ord> BTreeSet<T> {
+    /// Makes a new `BTreeSet` with a reasonable choice of B.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_mut)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set: BTreeSet<i32> = BTreeSet::new();
+    /// ```
+    #[stable(feature = "rust1", since = "1.0.0")]
+    pub fn new() -> BTreeSet<T> {
+        BTreeSet { map: BTreeMap::new() }
     }
 
     /// Constructs a double-ended iterator over a sub-range of elements in the set.
@@ -402,34 +1417,50 @@ impl<T: Ord> BTreeSet<T> {
     pub fn difference<'a>(&'a self, other: &'a BTreeSet<T>) -> Difference<'a, T> {
     */
 trait JustToIndentAsMuch<T> {
-    fn difference<'a>(&'a self, other: &'a BTreeSet<T>) -> Difference<'a, T>;
+    fn difference<'a, U, Q>(&'a self, other: &'a BTreeSet<U>) -> Difference<'a, T, U, Q>
+    where
+        T: Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized;
     fn symmetric_difference<'a>(&'a self, other: &'a BTreeSet<T>) -> SymmetricDifference<'a, T>;
-    fn intersection<'a>(&'a self, other: &'a BTreeSet<T>) -> Intersection<'a, T>;
+    fn intersection<'a, U, Q>(&'a self, other: &'a BTreeSet<U>) -> Intersection<'a, T, U, Q>
+    where
+        T: Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized;
     fn union<'a>(&'a self, other: &'a BTreeSet<T>) -> Union<'a, T>;
-    fn is_subset(&self, other: &BTreeSet<T>) -> bool;
+    fn is_subset<U, Q>(&self, other: &BTreeSet<U>) -> bool
+    where
+        T: Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized;
 }
 impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
-    fn difference<'a>(&'a self, other: &'a BTreeSet<T>) -> Difference<'a, T> {
+    fn difference<'a, U, Q>(&'a self, other: &'a BTreeSet<U>) -> Difference<'a, T, U, Q>
+    where
+        T: Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         let (self_min, self_max) = if let (Some(self_min), Some(self_max)) =
             (self.iter().next(), self.iter().next_back())
         {
-            (self_min, self_max)
+            (Borrow::<Q>::borrow(self_min), Borrow::<Q>::borrow(self_max))
         } else {
-            return Difference {
-                inner: DifferenceInner::Iterate(self.iter()),
-            };
+            return Difference::of_sets(DifferenceInner::Iterate(self.iter()), self, other);
         };
         let (other_min, other_max) = if let (Some(other_min), Some(other_max)) =
             (other.iter().next(), other.iter().next_back())
         {
-            (other_min, other_max)
+            (
+                Borrow::<Q>::borrow(other_min),
+                Borrow::<Q>::borrow(other_max),
+            )
         } else {
-            return Difference {
-                inner: DifferenceInner::Iterate(self.iter()),
-            };
+            return Difference::of_sets(DifferenceInner::Iterate(self.iter()), self, other);
         };
-        Difference {
-            inner: match (self_min.cmp(other_max), self_max.cmp(other_min)) {
+        Difference::of_sets(
+            match (self_min.cmp(other_max), self_max.cmp(other_min)) {
                 (Greater, _) | (_, Less) => DifferenceInner::Iterate(self.iter()),
                 (Equal, _) => {
                     let mut self_iter = self.iter();
@@ -443,16 +1474,40 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
                 }
                 _ if self.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF => {
                     DifferenceInner::Search {
-                        self_iter: self.iter(),
+                        self_prefix: self.range::<Q, _>((Unbounded, Excluded(other_min))),
+                        self_iter: self.range::<Q, _>((Included(other_min), Included(other_max))),
+                        self_suffix: self.range::<Q, _>((Excluded(other_max), Unbounded)),
                         other_set: other,
                     }
                 }
+                _ if other.len() <= self.len() / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF => {
+                    let mut other_iter =
+                        other.range::<Q, _>((Included(self_min), Included(self_max)));
+                    let other_next = other_iter.next();
+                    DifferenceInner::Splice {
+                        other_iter,
+                        other_next,
+                        self_set: self,
+                        self_segment: match other_next {
+                            Some(other_next) => {
+                                self.range::<Q, _>((Unbounded, Excluded(other_next.borrow())))
+                            }
+                            None => self.range::<Q, _>(..),
+                        },
+                    }
+                }
                 _ => DifferenceInner::Stitch {
-                    self_iter: self.iter(),
-                    other_iter: Peeking::new(other.iter()),
+                    self_prefix: self.range::<Q, _>((Unbounded, Excluded(other_min))),
+                    self_iter: self.range::<Q, _>((Included(other_min), Included(other_max))),
+                    self_suffix: self.range::<Q, _>((Excluded(other_max), Unbounded)),
+                    other_iter: Peeking::new(
+                        other.range::<Q, _>((Included(self_min), Included(self_max))),
+                    ),
                 },
             },
-        }
+            self,
+            other,
+        )
     }
 
     /// Visits the values representing the symmetric difference,
@@ -482,7 +1537,10 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
                                     -> SymmetricDifference<'a, T> {
     */
     fn symmetric_difference<'a>(&'a self, other: &'a BTreeSet<T>) -> SymmetricDifference<'a, T> {
-        SymmetricDifference(MergeIterInner::new(self.iter(), other.iter()))
+        SymmetricDifference {
+            merge: MergeInner::new(self, other),
+            lens: MergeLens::exact(self.len(), other.len()),
+        }
     }
 
     /*
@@ -509,48 +1567,61 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn intersection<'a>(&'a self, other: &'a BTreeSet<T>) -> Intersection<'a, T> {
     */
-    fn intersection<'a>(&'a self, other: &'a BTreeSet<T>) -> Intersection<'a, T> {
+    fn intersection<'a, U, Q>(&'a self, other: &'a BTreeSet<U>) -> Intersection<'a, T, U, Q>
+    where
+        T: Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         let (self_min, self_max) = if let (Some(self_min), Some(self_max)) =
             (self.iter().next(), self.iter().next_back())
         {
             (self_min, self_max)
         } else {
-            return Intersection {
-                inner: IntersectionInner::Answer(None),
-            };
+            return Intersection::of_sets(IntersectionInner::Answer(None), self, other);
         };
-        let (other_min, other_max) = if let (Some(other_min), Some(other_max)) =
+        let (other_first, other_last) = if let (Some(other_first), Some(other_last)) =
             (other.iter().next(), other.iter().next_back())
         {
-            (other_min, other_max)
+            (other_first, other_last)
         } else {
-            return Intersection {
-                inner: IntersectionInner::Answer(None),
-            };
+            return Intersection::of_sets(IntersectionInner::Answer(None), self, other);
         };
-        Intersection {
-            inner: match (self_min.cmp(other_max), self_max.cmp(other_min)) {
+        let (self_min_key, self_max_key) =
+            (Borrow::<Q>::borrow(self_min), Borrow::<Q>::borrow(self_max));
+        let (other_min, other_max) = (
+            Borrow::<Q>::borrow(other_first),
+            Borrow::<Q>::borrow(other_last),
+        );
+        let overlap = (
+            Included(max(self_min_key, other_min)),
+            Included(min(self_max_key, other_max)),
+        );
+        Intersection::of_sets(
+            match (self_min_key.cmp(other_max), self_max_key.cmp(other_min)) {
                 (Greater, _) | (_, Less) => IntersectionInner::Answer(None),
-                (Equal, _) => IntersectionInner::Answer(Some(self_min)),
-                (_, Equal) => IntersectionInner::Answer(Some(self_max)),
+                (Equal, _) => IntersectionInner::Answer(Some((self_min, other_last))),
+                (_, Equal) => IntersectionInner::Answer(Some((self_max, other_first))),
                 _ if self.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF => {
-                    IntersectionInner::Search {
-                        small_iter: self.iter(),
+                    IntersectionInner::SearchOther {
+                        small_iter: self.range::<Q, _>(overlap),
                         large_set: other,
                     }
                 }
                 _ if other.len() <= self.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF => {
-                    IntersectionInner::Search {
-                        small_iter: other.iter(),
+                    IntersectionInner::SearchSelf {
+                        small_iter: other.range::<Q, _>(overlap),
                         large_set: self,
                     }
                 }
                 _ => IntersectionInner::Stitch {
-                    a: self.iter(),
-                    b: other.iter(),
+                    a: self.range::<Q, _>(overlap),
+                    b: other.range::<Q, _>(overlap),
                 },
             },
-        }
+            self,
+            other,
+        )
     }
 
     /*
@@ -576,7 +1647,12 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
     pub fn union<'a>(&'a self, other: &'a BTreeSet<T>) -> Union<'a, T> {
     */
     fn union<'a>(&'a self, other: &'a BTreeSet<T>) -> Union<'a, T> {
-        Union(MergeIterInner::new(self.iter(), other.iter()))
+        Union {
+            merge: MergeInner::new(self, other),
+            lens: MergeLens::exact(self.len(), other.len()),
+            self_set: self,
+            other_set: other,
+        }
     }
 
     /*
@@ -685,7 +1761,12 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn is_subset(&self, other: &BTreeSet<T>) -> bool {
     */
-    fn is_subset(&self, other: &BTreeSet<T>) -> bool {
+    fn is_subset<U, Q>(&self, other: &BTreeSet<U>) -> bool
+    where
+        T: Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         // Same result as self.difference(other).next().is_none()
         // but the code below is faster (hugely in some cases).
         if self.len() > other.len() {
@@ -694,14 +1775,17 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
         let (self_min, self_max) = if let (Some(self_min), Some(self_max)) =
             (self.iter().next(), self.iter().next_back())
         {
-            (self_min, self_max)
+            (Borrow::<Q>::borrow(self_min), Borrow::<Q>::borrow(self_max))
         } else {
             return true; // self is empty
         };
         let (other_min, other_max) = if let (Some(other_min), Some(other_max)) =
             (other.iter().next(), other.iter().next_back())
         {
-            (other_min, other_max)
+            (
+                Borrow::<Q>::borrow(other_min),
+                Borrow::<Q>::borrow(other_max),
+            )
         } else {
             return false; // other is empty
         };
@@ -723,18 +1807,18 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
         if self_iter.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
             // Big difference in number of elements.
             for next in self_iter {
-                if !other.contains(next) {
+                if !other.contains(next.borrow()) {
                     return false;
                 }
             }
         } else {
             // Self is not much smaller than other set.
-            let mut other_iter = other.iter();
-            other_iter.next();
-            other_iter.next_back();
+            let mut other_iter = other.range::<Q, _>((Included(self_min), Included(self_max)));
             let mut self_next = self_iter.next();
             while let Some(self1) = self_next {
-                match other_iter.next().map_or(Less, |other1| self1.cmp(other1)) {
+                match other_iter.next().map_or(Less, |other1| {
+                    Borrow::<Q>::borrow(self1).cmp(other1.borrow())
+                }) {
                     Less => return false,
                     Equal => self_next = self_iter.next(),
                     Greater => (),
@@ -1258,89 +2342,395 @@ impl<T> FusedIterator for Range<'_, T> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
 */
-impl<T> Clone for Difference<'_, T> {
+impl<T, U, Q: ?Sized> Clone for Difference<'_, T, U, Q> {
     fn clone(&self) -> Self {
         Difference {
             inner: match &self.inner {
                 DifferenceInner::Stitch {
+                    self_prefix,
                     self_iter,
+                    self_suffix,
                     other_iter,
                 } => DifferenceInner::Stitch {
+                    self_prefix: self_prefix.clone(),
                     self_iter: self_iter.clone(),
+                    self_suffix: self_suffix.clone(),
                     other_iter: other_iter.clone(),
                 },
                 DifferenceInner::Search {
+                    self_prefix,
                     self_iter,
+                    self_suffix,
                     other_set,
                 } => DifferenceInner::Search {
+                    self_prefix: self_prefix.clone(),
                     self_iter: self_iter.clone(),
+                    self_suffix: self_suffix.clone(),
                     other_set,
                 },
+                DifferenceInner::Splice {
+                    other_iter,
+                    other_next,
+                    self_set,
+                    self_segment,
+                } => DifferenceInner::Splice {
+                    other_iter: other_iter.clone(),
+                    other_next: *other_next,
+                    self_set,
+                    self_segment: self_segment.clone(),
+                },
                 DifferenceInner::Iterate(iter) => DifferenceInner::Iterate(iter.clone()),
             },
+            lens: self.lens,
+            self_set: self.self_set,
+            other_set: self.other_set,
+            marker: PhantomData,
         }
     }
 }
-impl<'a, T: Ord> Iterator for Difference<'a, T> {
-    type Item = &'a T;
+impl<'a, T, U, Q> Difference<'a, T, U, Q>
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    /// Starts a difference of whole sets, whose lengths bound its own.
+    fn of_sets(
+        inner: DifferenceInner<'a, T, U>,
+        self_set: &'a BTreeSet<T>,
+        other_set: &'a BTreeSet<U>,
+    ) -> Self {
+        let lens = match &inner {
+            DifferenceInner::Iterate(iter) => MergeLens::exact(iter.len(), 0),
+            _ => MergeLens::exact(self_set.len(), other_set.len()),
+        };
+        Difference {
+            inner,
+            lens,
+            self_set,
+            other_set,
+            marker: PhantomData,
+        }
+    }
 
-    fn next(&mut self) -> Option<&'a T> {
+    /// Skips the elements below the bound, so that the next element returned
+    /// is the first one not below the bound. Repositions the underlying
+    /// iterators by searching the sets, rather than by stepping through them.
+    /// Does nothing if the iteration already went past the bound.
+    pub fn advance_to(&mut self, bound: &Q) {
+        let (self_set, other_set) = (self.self_set, self.other_set);
+        self.lens.skipped();
         match &mut self.inner {
             DifferenceInner::Stitch {
+                self_prefix,
+                self_iter,
+                self_suffix,
+                other_iter,
+                ..
+            } => {
+                advance_range(self_set, self_prefix, bound);
+                advance_range(self_set, self_iter, bound);
+                advance_range(self_set, self_suffix, bound);
+                advance_peeking(other_set, other_iter, bound);
+            }
+            DifferenceInner::Search {
+                self_prefix,
+                self_iter,
+                self_suffix,
+                ..
+            } => {
+                advance_range(self_set, self_prefix, bound);
+                advance_range(self_set, self_iter, bound);
+                advance_range(self_set, self_suffix, bound);
+            }
+            DifferenceInner::Splice {
+                other_iter,
+                other_next,
+                self_segment,
+                ..
+            } => {
+                if other_next.is_some_and(|other_next| other_next.borrow() < bound) {
+                    advance_range(other_set, other_iter, bound);
+                    *other_next = other_iter.next();
+                    *self_segment = match *other_next {
+                        Some(upper) => {
+                            self_set.range::<Q, _>((Included(bound), Excluded(upper.borrow())))
+                        }
+                        None => self_set.range::<Q, _>((Included(bound), Unbounded)),
+                    };
+                } else {
+                    advance_range(self_set, self_segment, bound);
+                }
+            }
+            DifferenceInner::Iterate(iter) => {
+                // Iter can't be repositioned, so stitch the range remaining
+                // against nothing instead.
+                if iter
+                    .clone()
+                    .next()
+                    .is_some_and(|first| first.borrow() < bound)
+                {
+                    let mut self_iter = match iter.clone().next_back() {
+                        Some(last) => self_set.range::<Q, _>((Unbounded, Included(last.borrow()))),
+                        None => empty_range(self_set, bound),
+                    };
+                    advance_range(self_set, &mut self_iter, bound);
+                    self.inner = DifferenceInner::Stitch {
+                        self_prefix: empty_range(self_set, bound),
+                        self_iter,
+                        self_suffix: empty_range(self_set, bound),
+                        other_iter: Peeking::new(empty_range(other_set, bound)),
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T, U> DifferenceInner<'a, T, U> {
+    /// Core of Difference::next, counting the elements it takes from self
+    /// as side a, and those it takes from other as side b.
+    fn next<Q>(&mut self, lens: &mut MergeLens) -> Option<&'a T>
+    where
+        T: Ord + Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self {
+            DifferenceInner::Stitch {
+                self_prefix,
                 self_iter,
+                self_suffix,
                 other_iter,
+                ..
             } => {
-                let mut self_next = self_iter.next()?;
+                if let Some(self_next) = lens.took_a(self_prefix.next()) {
+                    return Some(self_next);
+                }
+                let mut self_next = match lens.took_a(self_iter.next()) {
+                    Some(self_next) => self_next,
+                    None => return lens.took_a(self_suffix.next()),
+                };
                 loop {
-                    match other_iter
-                        .head
-                        .map_or(Less, |other_next| self_next.cmp(other_next))
-                    {
+                    match other_iter.head.map_or(Less, |other_next| {
+                        Borrow::<Q>::borrow(self_next).cmp(other_next.borrow())
+                    }) {
                         Less => return Some(self_next),
                         Equal => {
-                            self_next = self_iter.next()?;
-                            other_iter.next();
+                            lens.took_b(other_iter.next());
+                            self_next = match lens.took_a(self_iter.next()) {
+                                Some(self_next) => self_next,
+                                None => return lens.took_a(self_suffix.next()),
+                            };
                         }
                         Greater => {
-                            other_iter.next();
+                            lens.took_b(other_iter.next());
                         }
                     }
                 }
             }
             DifferenceInner::Search {
+                self_prefix,
                 self_iter,
+                self_suffix,
                 other_set,
+                ..
+            } => {
+                if let Some(self_next) = lens.took_a(self_prefix.next()) {
+                    return Some(self_next);
+                }
+                while let Some(self_next) = lens.took_a(self_iter.next()) {
+                    if !other_set.contains(self_next.borrow()) {
+                        return Some(self_next);
+                    }
+                }
+                lens.took_a(self_suffix.next())
+            }
+            DifferenceInner::Splice {
+                other_iter,
+                other_next,
+                self_set,
+                self_segment,
+                ..
             } => loop {
-                let self_next = self_iter.next()?;
-                if !other_set.contains(&self_next) {
+                if let Some(self_next) = lens.took_a(self_segment.next()) {
                     return Some(self_next);
                 }
+                // Leaves other uncounted: the segments skip the elements of
+                // self equal to those of other without counting them either,
+                // but there are never more of them than of other.
+                let lower = (*other_next)?;
+                *other_next = other_iter.next();
+                let lower = Excluded(lower.borrow());
+                *self_segment = match *other_next {
+                    Some(upper) => self_set.range::<Q, _>((lower, Excluded(upper.borrow()))),
+                    None => self_set.range::<Q, _>((lower, Unbounded)),
+                };
             },
-            DifferenceInner::Iterate(iter) => iter.next(),
+            DifferenceInner::Iterate(iter) => lens.took_a(iter.next()),
         }
     }
+}
+
+impl<'a, T, U, Q> Iterator for Difference<'a, T, U, Q>
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next::<Q>(&mut self.lens)
+    }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let (self_len, other_len) = match &self.inner {
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self.inner {
             DifferenceInner::Stitch {
+                self_prefix,
                 self_iter,
-                other_iter,
-            } => (self_iter.len(), other_iter.len()),
+                self_suffix,
+                mut other_iter,
+                ..
+            } => {
+                let acc = self_prefix.fold(init, &mut f);
+                let acc = self_iter.fold(acc, |acc, self_next| loop {
+                    match other_iter.head.map_or(Less, |other_next| {
+                        Borrow::<Q>::borrow(self_next).cmp(other_next.borrow())
+                    }) {
+                        Less => return f(acc, self_next),
+                        Equal => {
+                            other_iter.next();
+                            return acc;
+                        }
+                        Greater => {
+                            other_iter.next();
+                        }
+                    }
+                });
+                self_suffix.fold(acc, f)
+            }
             DifferenceInner::Search {
+                self_prefix,
                 self_iter,
+                self_suffix,
                 other_set,
-            } => (self_iter.len(), other_set.len()),
-            DifferenceInner::Iterate(iter) => (iter.len(), 0),
-        };
-        (self_len.saturating_sub(other_len), Some(self_len))
+                ..
+            } => {
+                let acc = self_prefix.fold(init, &mut f);
+                let acc = self_iter
+                    .filter(|self_next| !other_set.contains(Borrow::<Q>::borrow(*self_next)))
+                    .fold(acc, &mut f);
+                self_suffix.fold(acc, f)
+            }
+            DifferenceInner::Splice {
+                mut other_iter,
+                mut other_next,
+                self_set,
+                self_segment,
+                ..
+            } => {
+                let mut acc = self_segment.fold(init, &mut f);
+                while let Some(lower) = other_next {
+                    other_next = other_iter.next();
+                    let lower = Excluded(lower.borrow());
+                    let self_segment = match other_next {
+                        Some(upper) => self_set.range::<Q, _>((lower, Excluded(upper.borrow()))),
+                        None => self_set.range::<Q, _>((lower, Unbounded)),
+                    };
+                    acc = self_segment.fold(acc, &mut f);
+                }
+                acc
+            }
+            DifferenceInner::Iterate(iter) => iter.fold(init, f),
+        }
+    }
+
+    fn last(self) -> Option<&'a T> {
+        let other_set = self.other_set;
+        let not_in_other = |self_next: &&'a T| !other_set.contains(Borrow::<Q>::borrow(*self_next));
+        match self.inner {
+            DifferenceInner::Stitch {
+                mut self_prefix,
+                mut self_iter,
+                mut self_suffix,
+                mut other_iter,
+                ..
+            } => {
+                if let Some(self_last) = self_suffix.next_back() {
+                    return Some(self_last);
+                }
+                let mut other_last = other_iter.next_back();
+                while let Some(self_last) = self_iter.next_back() {
+                    let self_key = self_last.borrow();
+                    while other_last.is_some_and(|other_last| other_last.borrow() > self_key) {
+                        other_last = other_iter.next_back();
+                    }
+                    if other_last.is_none_or(|other_last| other_last.borrow() != self_key) {
+                        return Some(self_last);
+                    }
+                }
+                self_prefix.next_back()
+            }
+            DifferenceInner::Search {
+                mut self_prefix,
+                self_iter,
+                mut self_suffix,
+                ..
+            } => self_suffix
+                .next_back()
+                .or_else(|| self_iter.rev().find(not_in_other))
+                .or_else(|| self_prefix.next_back()),
+            DifferenceInner::Splice {
+                other_next,
+                self_set,
+                mut self_segment,
+                ..
+            } => {
+                // Self's elements left are those in the current segment,
+                // and those beyond the element of other bounding it.
+                if let Some(lower) = other_next {
+                    let self_rest = self_set.range::<Q, _>((Excluded(lower.borrow()), Unbounded));
+                    if let Some(self_last) = self_rest.rev().find(not_in_other) {
+                        return Some(self_last);
+                    }
+                }
+                self_segment.next_back()
+            }
+            DifferenceInner::Iterate(iter) => iter.last(),
+        }
+    }
+
+    fn min(mut self) -> Option<&'a T> {
+        self.next()
+    }
+
+    fn max(self) -> Option<&'a T> {
+        self.last()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lens.difference_hint()
     }
 }
 
-impl<T: Ord> FusedIterator for Difference<'_, T> {}
+impl<T, U, Q> FusedIterator for Difference<'_, T, U, Q>
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+}
 
 impl<T> Clone for SymmetricDifference<'_, T> {
     fn clone(&self) -> Self {
-        SymmetricDifference(self.0.clone())
+        SymmetricDifference {
+            merge: self.merge.clone(),
+            lens: self.lens,
+        }
     }
 }
 impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
@@ -1348,120 +2738,896 @@ impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
 
     fn next(&mut self) -> Option<&'a T> {
         loop {
-            let (a_next, b_next) = self.0.nexts();
+            let nexts = self.merge.nexts();
+            self.lens.took(&nexts);
+            let (a_next, b_next) = nexts;
             if a_next.and(b_next).is_none() {
                 return a_next.or(b_next);
             }
         }
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.merge.fold(init, |acc, nexts| match nexts {
+            (Some(next), None) | (None, Some(next)) => f(acc, next),
+            _ => acc,
+        })
+    }
+
+    fn last(self) -> Option<&'a T> {
+        let (a_last, b_last) = self
+            .merge
+            .rfind(|(a_last, b_last)| a_last.and(*b_last).is_none())?;
+        a_last.or(b_last)
+    }
+
+    fn min(mut self) -> Option<&'a T> {
+        self.next()
+    }
+
+    fn max(self) -> Option<&'a T> {
+        self.last()
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (a_len, b_len) = self.0.lens();
-        // No checked_add, because even if a and b refer to the same set,
-        // and T is an empty type, the storage overhead of sets limits
-        // the number of elements to less than half the range of usize.
-        (0, Some(a_len + b_len))
+        self.lens.symmdiff_hint()
     }
 }
 
 impl<T: Ord> FusedIterator for SymmetricDifference<'_, T> {}
 
-impl<T> Clone for Intersection<'_, T> {
+impl<T, U> Clone for IntersectionInner<'_, T, U> {
     fn clone(&self) -> Self {
-        Intersection {
-            inner: match &self.inner {
-                IntersectionInner::Stitch { a, b } => IntersectionInner::Stitch {
-                    a: a.clone(),
-                    b: b.clone(),
-                },
-                IntersectionInner::Search {
-                    small_iter,
-                    large_set,
-                } => IntersectionInner::Search {
-                    small_iter: small_iter.clone(),
-                    large_set,
-                },
-                IntersectionInner::Answer(answer) => IntersectionInner::Answer(*answer),
+        match self {
+            IntersectionInner::Stitch { a, b } => IntersectionInner::Stitch {
+                a: a.clone(),
+                b: b.clone(),
+            },
+            IntersectionInner::SearchOther {
+                small_iter,
+                large_set,
+            } => IntersectionInner::SearchOther {
+                small_iter: small_iter.clone(),
+                large_set,
+            },
+            IntersectionInner::SearchSelf {
+                small_iter,
+                large_set,
+            } => IntersectionInner::SearchSelf {
+                small_iter: small_iter.clone(),
+                large_set,
             },
+            IntersectionInner::Answer(answer) => IntersectionInner::Answer(*answer),
         }
     }
 }
-impl<'a, T: Ord> Iterator for Intersection<'a, T> {
-    type Item = &'a T;
-
-    fn next(&mut self) -> Option<&'a T> {
-        match &mut self.inner {
-            IntersectionInner::Stitch { a, b } => {
-                let mut a_next = a.next()?;
-                let mut b_next = b.next()?;
+impl<'a, T, U> IntersectionInner<'a, T, U> {
+    /// Core of Intersection and IntersectionPairs,
+    /// returning the matching elements on both sides, and counting
+    /// the elements it takes from either side.
+    fn nexts<Q>(&mut self, lens: &mut MergeLens) -> Option<(&'a T, &'a U)>
+    where
+        T: Ord + Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self {
+            IntersectionInner::Stitch { a, b, .. } => {
+                let mut a_next = lens.took_a(a.next())?;
+                let mut b_next = lens.took_b(b.next())?;
                 loop {
-                    match a_next.cmp(b_next) {
-                        Less => a_next = a.next()?,
-                        Greater => b_next = b.next()?,
-                        Equal => return Some(a_next),
+                    match Borrow::<Q>::borrow(a_next).cmp(b_next.borrow()) {
+                        Less => a_next = lens.took_a(a.next())?,
+                        Greater => b_next = lens.took_b(b.next())?,
+                        Equal => return Some((a_next, b_next)),
                     }
                 }
             }
-            IntersectionInner::Search {
+            IntersectionInner::SearchOther {
+                small_iter,
+                large_set,
+                ..
+            } => loop {
+                let small_next = lens.took_a(small_iter.next())?;
+                if let Some(large_next) = large_set.get(small_next.borrow()) {
+                    return Some((small_next, large_next));
+                }
+            },
+            IntersectionInner::SearchSelf {
                 small_iter,
                 large_set,
+                ..
             } => loop {
-                let small_next = small_iter.next()?;
-                if large_set.contains(&small_next) {
-                    return Some(small_next);
+                let small_next = lens.took_b(small_iter.next())?;
+                if let Some(large_next) = large_set.get(small_next.borrow()) {
+                    return Some((large_next, small_next));
                 }
             },
             IntersectionInner::Answer(answer) => answer.take(),
         }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        match &self.inner {
-            IntersectionInner::Stitch { a, b } => (0, Some(min(a.len(), b.len()))),
-            IntersectionInner::Search { small_iter, .. } => (0, Some(small_iter.len())),
+    /// Like nexts(), folding all remaining matches.
+    fn fold<Q, B, F>(self, init: B, mut f: F) -> B
+    where
+        T: Ord + Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized,
+        F: FnMut(B, (&'a T, &'a U)) -> B,
+    {
+        match self {
+            IntersectionInner::Stitch { mut a, mut b, .. } => {
+                let mut acc = init;
+                let (mut a_next, mut b_next) = match (a.next(), b.next()) {
+                    (Some(a_next), Some(b_next)) => (a_next, b_next),
+                    _ => return acc,
+                };
+                loop {
+                    match Borrow::<Q>::borrow(a_next).cmp(b_next.borrow()) {
+                        Less => match a.next() {
+                            Some(next) => a_next = next,
+                            None => return acc,
+                        },
+                        Greater => match b.next() {
+                            Some(next) => b_next = next,
+                            None => return acc,
+                        },
+                        Equal => {
+                            acc = f(acc, (a_next, b_next));
+                            match (a.next(), b.next()) {
+                                (Some(a1), Some(b1)) => {
+                                    a_next = a1;
+                                    b_next = b1;
+                                }
+                                _ => return acc,
+                            }
+                        }
+                    }
+                }
+            }
+            IntersectionInner::SearchOther {
+                small_iter,
+                large_set,
+                ..
+            } => small_iter.fold(init, |acc, small_next| {
+                match large_set.get(small_next.borrow()) {
+                    Some(large_next) => f(acc, (small_next, large_next)),
+                    None => acc,
+                }
+            }),
+            IntersectionInner::SearchSelf {
+                small_iter,
+                large_set,
+                ..
+            } => small_iter.fold(init, |acc, small_next| {
+                match large_set.get(small_next.borrow()) {
+                    Some(large_next) => f(acc, (large_next, small_next)),
+                    None => acc,
+                }
+            }),
+            IntersectionInner::Answer(answer) => answer.into_iter().fold(init, f),
+        }
+    }
+
+    /// Like nexts(), returning the last match left.
+    fn last<Q>(self) -> Option<(&'a T, &'a U)>
+    where
+        T: Ord + Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self {
+            IntersectionInner::Stitch { mut a, mut b, .. } => {
+                let mut a_last = a.next_back()?;
+                let mut b_last = b.next_back()?;
+                loop {
+                    match Borrow::<Q>::borrow(a_last).cmp(b_last.borrow()) {
+                        Less => b_last = b.next_back()?,
+                        Greater => a_last = a.next_back()?,
+                        Equal => return Some((a_last, b_last)),
+                    }
+                }
+            }
+            IntersectionInner::SearchOther {
+                small_iter,
+                large_set,
+                ..
+            } => small_iter.rev().find_map(|small_last| {
+                let large_last = large_set.get(small_last.borrow())?;
+                Some((small_last, large_last))
+            }),
+            IntersectionInner::SearchSelf {
+                small_iter,
+                large_set,
+                ..
+            } => small_iter.rev().find_map(|small_last| {
+                let large_last = large_set.get(small_last.borrow())?;
+                Some((large_last, small_last))
+            }),
+            IntersectionInner::Answer(answer) => answer,
+        }
+    }
+
+    fn advance_to<Q>(&mut self, a_set: &'a BTreeSet<T>, b_set: &'a BTreeSet<U>, bound: &Q)
+    where
+        T: Ord + Borrow<Q>,
+        U: Ord + Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self {
+            IntersectionInner::Stitch { a, b, .. } => {
+                advance_range(a_set, a, bound);
+                advance_range(b_set, b, bound);
+            }
+            IntersectionInner::SearchOther { small_iter, .. } => {
+                advance_range(a_set, small_iter, bound)
+            }
+            IntersectionInner::SearchSelf { small_iter, .. } => {
+                advance_range(b_set, small_iter, bound)
+            }
+            IntersectionInner::Answer(answer) => {
+                if answer.is_some_and(|(a_next, _)| a_next.borrow() < bound) {
+                    *answer = None;
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self, lens: &MergeLens) -> (usize, Option<usize>) {
+        match self {
             IntersectionInner::Answer(None) => (0, Some(0)),
             IntersectionInner::Answer(Some(_)) => (1, Some(1)),
+            _ => lens.intersection_hint(),
+        }
+    }
+}
+
+impl<T, U, Q: ?Sized> Clone for Intersection<'_, T, U, Q> {
+    fn clone(&self) -> Self {
+        Intersection {
+            inner: self.inner.clone(),
+            lens: self.lens,
+            self_set: self.self_set,
+            other_set: self.other_set,
+            marker: PhantomData,
+        }
+    }
+}
+impl<'a, T, U, Q> Intersection<'a, T, U, Q>
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    /// Starts an intersection of whole sets, whose lengths bound its own.
+    fn of_sets(
+        inner: IntersectionInner<'a, T, U>,
+        self_set: &'a BTreeSet<T>,
+        other_set: &'a BTreeSet<U>,
+    ) -> Self {
+        Intersection {
+            inner,
+            lens: MergeLens::exact(self_set.len(), other_set.len()),
+            self_set,
+            other_set,
+            marker: PhantomData,
         }
     }
+
+    /// Skips the elements below the bound, so that the next element returned
+    /// is the first one not below the bound. Repositions the underlying
+    /// iterators by searching the sets, rather than by stepping through them.
+    /// Does nothing if the iteration already went past the bound.
+    pub fn advance_to(&mut self, bound: &Q) {
+        self.inner.advance_to(self.self_set, self.other_set, bound);
+        self.lens.skipped();
+    }
 }
 
-impl<T: Ord> FusedIterator for Intersection<'_, T> {}
+impl<'a, T, U, Q> Iterator for Intersection<'a, T, U, Q>
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let (self_next, _) = self.inner.nexts::<Q>(&mut self.lens)?;
+        Some(self_next)
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner
+            .fold::<Q, _, _>(init, |acc, (self_next, _)| f(acc, self_next))
+    }
+
+    fn last(self) -> Option<&'a T> {
+        self.inner.last::<Q>().map(|(self_last, _)| self_last)
+    }
+
+    fn min(mut self) -> Option<&'a T> {
+        self.next()
+    }
+
+    fn max(self) -> Option<&'a T> {
+        self.last()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint(&self.lens)
+    }
+}
+
+impl<T, U, Q> FusedIterator for Intersection<'_, T, U, Q>
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+}
+
+impl<T, U, Q: ?Sized> Clone for IntersectionPairs<'_, T, U, Q> {
+    fn clone(&self) -> Self {
+        IntersectionPairs {
+            inner: self.inner.clone(),
+            lens: self.lens,
+            marker: PhantomData,
+        }
+    }
+}
+impl<'a, T, U, Q> Iterator for IntersectionPairs<'a, T, U, Q>
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    type Item = (&'a T, &'a U);
+
+    fn next(&mut self) -> Option<(&'a T, &'a U)> {
+        self.inner.nexts::<Q>(&mut self.lens)
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.fold::<Q, _, _>(init, f)
+    }
+
+    fn last(self) -> Option<(&'a T, &'a U)> {
+        self.inner.last::<Q>()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint(&self.lens)
+    }
+}
+
+impl<T, U, Q> FusedIterator for IntersectionPairs<'_, T, U, Q>
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+}
 
 impl<T> Clone for Union<'_, T> {
     fn clone(&self) -> Self {
-        Union(self.0.clone())
+        Union {
+            merge: self.merge.clone(),
+            lens: self.lens,
+            self_set: self.self_set,
+            other_set: self.other_set,
+        }
+    }
+}
+impl<'a, T: Ord> Union<'a, T> {
+    /// Skips the elements below the bound, so that the next element returned
+    /// is the first one not below the bound. Repositions the underlying
+    /// iterators by searching the sets, rather than by stepping through them.
+    /// Does nothing if the iteration already went past the bound.
+    pub fn advance_to(&mut self, bound: &T) {
+        self.merge.advance_to(self.self_set, self.other_set, bound);
+        self.lens.skipped();
     }
 }
+
 impl<'a, T: Ord> Iterator for Union<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
-        let (a_next, b_next) = self.0.nexts();
+        let nexts = self.merge.nexts();
+        self.lens.took(&nexts);
+        let (a_next, b_next) = nexts;
         a_next.or(b_next)
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.merge.fold(init, |acc, nexts| match nexts {
+            (Some(next), _) | (None, Some(next)) => f(acc, next),
+            (None, None) => acc,
+        })
+    }
+
+    fn last(self) -> Option<&'a T> {
+        // Simply the greater of the last elements on either side.
+        let (a_last, b_last) = self.merge.rfind(|_| true)?;
+        a_last.or(b_last)
+    }
+
+    fn min(mut self) -> Option<&'a T> {
+        self.next()
+    }
+
+    fn max(self) -> Option<&'a T> {
+        self.last()
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (a_len, b_len) = self.0.lens();
-        // No checked_add - see SymmetricDifference::size_hint.
-        (max(a_len, b_len), Some(a_len + b_len))
+        self.lens.union_hint()
     }
 }
 
 impl<T: Ord> FusedIterator for Union<'_, T> {}
 
+impl<T> Clone for Classify<'_, T> {
+    fn clone(&self) -> Self {
+        Classify {
+            merge: self.merge.clone(),
+            skip_both: self.skip_both,
+            lens: self.lens,
+        }
+    }
+}
+impl<'a, T: Ord> Iterator for Classify<'a, T> {
+    type Item = Merged<'a, T>;
+
+    fn next(&mut self) -> Option<Merged<'a, T>> {
+        let nexts = if self.skip_both {
+            self.merge.nexts_unequal()
+        } else {
+            self.merge.nexts()
+        };
+        self.lens.took(&nexts);
+        match nexts {
+            (Some(a_next), None) => Some(Merged::Left(a_next)),
+            (Some(a_next), Some(b_next)) => Some(Merged::Both(a_next, b_next)),
+            (None, Some(b_next)) => Some(Merged::Right(b_next)),
+            (None, None) => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.skip_both {
+            self.lens.symmdiff_hint()
+        } else {
+            self.lens.union_hint()
+        }
+    }
+}
+
+impl<T: Ord> FusedIterator for Classify<'_, T> {}
+
+impl<'a, T, A, B> Clone for ViewDifference<'a, T, A, B>
+where
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T> + Clone,
+{
+    fn clone(&self) -> Self {
+        ViewDifference {
+            inner: match &self.inner {
+                ViewDifferenceInner::Stitch { a_iter, b_iter } => ViewDifferenceInner::Stitch {
+                    a_iter: a_iter.clone(),
+                    b_iter: b_iter.clone(),
+                },
+                ViewDifferenceInner::Search { a_iter, b } => ViewDifferenceInner::Search {
+                    a_iter: a_iter.clone(),
+                    b: b.clone(),
+                },
+            },
+        }
+    }
+}
+impl<'a, T, A, B> Iterator for ViewDifference<'a, T, A, B>
+where
+    T: Ord + 'a,
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match &mut self.inner {
+            ViewDifferenceInner::Stitch { a_iter, b_iter } => loop {
+                let a_next = a_iter.next()?;
+                loop {
+                    match b_iter.head.map_or(Less, |b_next| a_next.cmp(b_next)) {
+                        Less => return Some(a_next),
+                        Equal => {
+                            b_iter.next();
+                            break;
+                        }
+                        Greater => {
+                            b_iter.next();
+                        }
+                    }
+                }
+            },
+            ViewDifferenceInner::Search { a_iter, b } => loop {
+                let a_next = a_iter.next()?;
+                if b.contains(a_next) != Some(true) {
+                    return Some(a_next);
+                }
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            ViewDifferenceInner::Stitch { a_iter, .. } => (0, a_iter.size_hint().1),
+            ViewDifferenceInner::Search { a_iter, .. } => (0, a_iter.size_hint().1),
+        }
+    }
+}
+
+impl<'a, T, A, B> FusedIterator for ViewDifference<'a, T, A, B>
+where
+    T: Ord + 'a,
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T>,
+{
+}
+
+impl<'a, T, A, B> Clone for ViewIntersection<'a, T, A, B>
+where
+    A: SortedView<'a, T> + Clone,
+    B: SortedView<'a, T> + Clone,
+{
+    fn clone(&self) -> Self {
+        ViewIntersection {
+            inner: match &self.inner {
+                ViewIntersectionInner::Stitch { a_iter, b_iter } => ViewIntersectionInner::Stitch {
+                    a_iter: a_iter.clone(),
+                    b_iter: b_iter.clone(),
+                },
+                ViewIntersectionInner::SearchB { a_iter, b } => ViewIntersectionInner::SearchB {
+                    a_iter: a_iter.clone(),
+                    b: b.clone(),
+                },
+                ViewIntersectionInner::SearchA { b_iter, a } => ViewIntersectionInner::SearchA {
+                    b_iter: b_iter.clone(),
+                    a: a.clone(),
+                },
+            },
+        }
+    }
+}
+impl<'a, T, A, B> Iterator for ViewIntersection<'a, T, A, B>
+where
+    T: Ord + 'a,
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match &mut self.inner {
+            ViewIntersectionInner::Stitch { a_iter, b_iter } => {
+                let mut a_next = a_iter.next()?;
+                let mut b_next = b_iter.next()?;
+                loop {
+                    match a_next.cmp(b_next) {
+                        Less => a_next = a_iter.next()?,
+                        Greater => b_next = b_iter.next()?,
+                        Equal => return Some(a_next),
+                    }
+                }
+            }
+            ViewIntersectionInner::SearchB { a_iter, b } => loop {
+                let a_next = a_iter.next()?;
+                if b.contains(a_next) == Some(true) {
+                    return Some(a_next);
+                }
+            },
+            ViewIntersectionInner::SearchA { b_iter, a } => loop {
+                let b_next = b_iter.next()?;
+                if a.contains(b_next) == Some(true) {
+                    return Some(b_next);
+                }
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            ViewIntersectionInner::Stitch { a_iter, b_iter } => {
+                let max_len = match (a_iter.size_hint().1, b_iter.size_hint().1) {
+                    (Some(a_len), Some(b_len)) => Some(min(a_len, b_len)),
+                    (a_len, b_len) => a_len.or(b_len),
+                };
+                (0, max_len)
+            }
+            ViewIntersectionInner::SearchB { a_iter, .. } => (0, a_iter.size_hint().1),
+            ViewIntersectionInner::SearchA { b_iter, .. } => (0, b_iter.size_hint().1),
+        }
+    }
+}
+
+impl<'a, T, A, B> FusedIterator for ViewIntersection<'a, T, A, B>
+where
+    T: Ord + 'a,
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T>,
+{
+}
+
+impl<'a, T, A, B> Clone for ViewUnion<'a, T, A, B>
+where
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T>,
+{
+    fn clone(&self) -> Self {
+        ViewUnion {
+            a_iter: self.a_iter.clone(),
+            b_iter: self.b_iter.clone(),
+        }
+    }
+}
+impl<'a, T, A, B> Iterator for ViewUnion<'a, T, A, B>
+where
+    T: Ord + 'a,
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match (self.a_iter.head, self.b_iter.head) {
+            (Some(a_next), Some(b_next)) => match a_next.cmp(b_next) {
+                Less => self.a_iter.next(),
+                Equal => {
+                    self.b_iter.next();
+                    self.a_iter.next()
+                }
+                Greater => self.b_iter.next(),
+            },
+            (Some(_), None) => self.a_iter.next(),
+            (None, _) => self.b_iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let a_len = self.a_iter.tail.size_hint().1.map(|len| len + 1);
+        let b_len = self.b_iter.tail.size_hint().1.map(|len| len + 1);
+        (0, a_len.and_then(|a_len| b_len?.checked_add(a_len)))
+    }
+}
+
+impl<'a, T, A, B> FusedIterator for ViewUnion<'a, T, A, B>
+where
+    T: Ord + 'a,
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T>,
+{
+}
+
+impl<'a, A, B, K, FA, FB> Iterator for IntersectionByKey<'a, A, B, K, FA, FB>
+where
+    K: Ord,
+    FA: FnMut(&A) -> K,
+    FB: FnMut(&B) -> K,
+{
+    type Item = (&'a A, &'a B);
+
+    fn next(&mut self) -> Option<(&'a A, &'a B)> {
+        loop {
+            let (a_next, a_key) = self.a_head.as_ref()?;
+            if let Some(b_next) = self.b_pair.next() {
+                if (self.key_b)(b_next) == *a_key {
+                    return Some((a_next, b_next));
+                }
+            }
+            // Done pairing a_next, which may share its key with the next one.
+            self.next_a();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let max_len = self
+            .a_iter
+            .len()
+            .checked_mul(self.b_run.len())
+            .and_then(|len| len.checked_add(self.b_pair.len()));
+        (0, max_len)
+    }
+}
+
+impl<'a, A, B, K, FA, FB> IntersectionByKey<'a, A, B, K, FA, FB>
+where
+    K: Ord,
+    FA: FnMut(&A) -> K,
+    FB: FnMut(&B) -> K,
+{
+    // Moves on to the next element of a and the elements of b to pair it with.
+    fn next_a(&mut self) {
+        self.a_head = self
+            .a_iter
+            .next()
+            .map(|a_next| (a_next, (self.key_a)(a_next)));
+        if let Some((_, a_key)) = &self.a_head {
+            while let Some(b_next) = self.b_run.clone().next() {
+                if (self.key_b)(b_next) >= *a_key {
+                    break;
+                }
+                self.b_run.next();
+            }
+            self.b_pair = self.b_run.clone();
+        }
+    }
+}
+
+impl<A, B, K, FA, FB> FusedIterator for IntersectionByKey<'_, A, B, K, FA, FB>
+where
+    K: Ord,
+    FA: FnMut(&A) -> K,
+    FB: FnMut(&B) -> K,
+{
+}
+
+impl<'a, A, B, K, FA, FB> Iterator for DifferenceByKey<'a, A, B, FA, FB>
+where
+    K: Ord,
+    FA: FnMut(&A) -> K,
+    FB: FnMut(&B) -> K,
+{
+    type Item = &'a A;
+
+    fn next(&mut self) -> Option<&'a A> {
+        loop {
+            let a_next = self.a_iter.next()?;
+            let a_key = (self.key_a)(a_next);
+            loop {
+                match self
+                    .b_iter
+                    .head
+                    .map_or(Less, |b_next| a_key.cmp(&(self.key_b)(b_next)))
+                {
+                    Less => return Some(a_next),
+                    Equal => break,
+                    Greater => {
+                        self.b_iter.next();
+                    }
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.a_iter.len()))
+    }
+}
+
+impl<A, B, K, FA, FB> FusedIterator for DifferenceByKey<'_, A, B, FA, FB>
+where
+    K: Ord,
+    FA: FnMut(&A) -> K,
+    FB: FnMut(&B) -> K,
+{
+}
+
 pub fn is_subset<'a, T: Ord>(selve: &'a BTreeSet<T>, other: &'a BTreeSet<T>) -> bool {
-    (selve as &dyn JustToIndentAsMuch<T>).is_subset(other)
+    is_subset_borrowed::<T, _, _>(selve, other)
 }
 
-pub fn difference<'a, T: Ord>(selve: &'a BTreeSet<T>, other: &'a BTreeSet<T>) -> Difference<'a, T> {
-    (selve as &dyn JustToIndentAsMuch<T>).difference(other)
+pub fn is_subset_borrowed<'a, Q, T, U>(selve: &'a BTreeSet<T>, other: &'a BTreeSet<U>) -> bool
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    JustToIndentAsMuch::is_subset(selve, other)
+}
+
+pub fn difference<'a, T, A, B>(selve: A, other: B) -> A::Difference
+where
+    T: 'a,
+    A: SortedViews<'a, T, B>,
+{
+    selve.difference(other)
 }
 
-pub fn intersection<'a, T: Ord>(
+pub fn difference_borrowed<'a, Q, T, U>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<U>,
+) -> Difference<'a, T, U, Q>
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    JustToIndentAsMuch::difference(selve, other)
+}
+
+pub fn difference_stitch<'a, T: Ord>(
     selve: &'a BTreeSet<T>,
     other: &'a BTreeSet<T>,
-) -> Intersection<'a, T> {
-    (selve as &dyn JustToIndentAsMuch<T>).intersection(other)
+) -> Difference<'a, T> {
+    let empty = match selve.iter().next() {
+        Some(self_min) => empty_range(selve, self_min),
+        None => selve.range(..),
+    };
+    let inner = DifferenceInner::Stitch {
+        self_prefix: empty.clone(),
+        self_iter: selve.range(..),
+        self_suffix: empty,
+        other_iter: Peeking::new(other.range(..)),
+    };
+    Difference::of_sets(inner, selve, other)
+}
+
+pub fn difference_splice<'a, T: Ord>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<T>,
+) -> Difference<'a, T> {
+    let mut other_iter = other.range(..);
+    let other_next = other_iter.next();
+    let inner = DifferenceInner::Splice {
+        other_iter,
+        other_next,
+        self_set: selve,
+        self_segment: match other_next {
+            Some(other_next) => selve.range(..other_next),
+            None => selve.range(..),
+        },
+    };
+    Difference::of_sets(inner, selve, other)
+}
+
+pub fn intersection<'a, T, A, B>(selve: A, other: B) -> A::Intersection
+where
+    T: 'a,
+    A: SortedViews<'a, T, B>,
+{
+    selve.intersection(other)
+}
+
+pub fn intersection_borrowed<'a, Q, T, U>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<U>,
+) -> Intersection<'a, T, U, Q>
+where
+    T: Ord + Borrow<Q>,
+    U: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    JustToIndentAsMuch::intersection(selve, other)
+}
+
+pub fn intersection_pairs<'a, T: Ord>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<T>,
+) -> IntersectionPairs<'a, T> {
+    let Intersection {
+        inner,
+        lens,
+        marker,
+        ..
+    } = intersection(selve, other);
+    IntersectionPairs {
+        inner,
+        lens,
+        marker,
+    }
 }
 
 pub fn intersection_search<'a, T: Ord>(
@@ -1469,33 +3635,541 @@ pub fn intersection_search<'a, T: Ord>(
     large: &'a BTreeSet<T>,
 ) -> Intersection<'a, T> {
     assert!(small.len() <= large.len());
-    Intersection {
-        inner: IntersectionInner::Search {
-            small_iter: small.iter(),
-            large_set: &large,
-        },
-    }
+    let inner = IntersectionInner::SearchOther {
+        small_iter: small.range(..),
+        large_set: large,
+    };
+    Intersection::of_sets(inner, small, large)
 }
 
 pub fn intersection_stitch<'a, T: Ord>(
     a: &'a BTreeSet<T>,
     b: &'a BTreeSet<T>,
 ) -> Intersection<'a, T> {
-    Intersection {
-        inner: IntersectionInner::Stitch {
-            a: a.iter(),
-            b: b.iter(),
+    let inner = IntersectionInner::Stitch {
+        a: a.range(..),
+        b: b.range(..),
+    };
+    Intersection::of_sets(inner, a, b)
+}
+
+pub fn symmdiff<'a, T: Ord>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<T>,
+) -> SymmetricDifference<'a, T> {
+    JustToIndentAsMuch::symmetric_difference(selve, other)
+}
+
+pub fn symmdiff_stitch<'a, T: Ord>(
+    a: &'a BTreeSet<T>,
+    b: &'a BTreeSet<T>,
+) -> SymmetricDifference<'a, T> {
+    SymmetricDifference {
+        merge: MergeInner::Stitch(MergeIterInner::new(a, b)),
+        lens: MergeLens::exact(a.len(), b.len()),
+    }
+}
+
+pub fn symmdiff_splice<'a, T: Ord>(
+    small: &'a BTreeSet<T>,
+    large: &'a BTreeSet<T>,
+) -> SymmetricDifference<'a, T> {
+    SymmetricDifference {
+        merge: MergeInner::Splice(MergeSpliceInner::new(small, MergeIterSide::A, large)),
+        lens: MergeLens::exact(small.len(), large.len()),
+    }
+}
+
+pub fn union<'a, T, A, B>(selve: A, other: B) -> A::Union
+where
+    T: 'a,
+    A: SortedViews<'a, T, B>,
+{
+    selve.union(other)
+}
+
+pub fn union_stitch<'a, T: Ord>(a: &'a BTreeSet<T>, b: &'a BTreeSet<T>) -> Union<'a, T> {
+    Union {
+        merge: MergeInner::Stitch(MergeIterInner::new(a, b)),
+        lens: MergeLens::exact(a.len(), b.len()),
+        self_set: a,
+        other_set: b,
+    }
+}
+
+pub fn union_splice<'a, T: Ord>(small: &'a BTreeSet<T>, large: &'a BTreeSet<T>) -> Union<'a, T> {
+    Union {
+        merge: MergeInner::Splice(MergeSpliceInner::new(small, MergeIterSide::A, large)),
+        lens: MergeLens::exact(small.len(), large.len()),
+        self_set: small,
+        other_set: large,
+    }
+}
+
+// Classified merge, telling difference, reverse difference and intersection
+// apart in a single pass. In change detection, where the sets tend to be
+// nearly equal, classify_changes leaves out the elements present in both.
+
+pub fn classify<'a, T: Ord>(a: &'a BTreeSet<T>, b: &'a BTreeSet<T>) -> Classify<'a, T> {
+    Classify {
+        merge: MergeInner::new(a, b),
+        skip_both: false,
+        lens: MergeLens::exact(a.len(), b.len()),
+    }
+}
+
+pub fn classify_changes<'a, T: Ord>(a: &'a BTreeSet<T>, b: &'a BTreeSet<T>) -> Classify<'a, T> {
+    Classify {
+        merge: MergeInner::new(a, b),
+        skip_both: true,
+        lens: MergeLens::exact(a.len(), b.len()),
+    }
+}
+
+// Operations restricted to the elements within a range. Ranges don't know
+// their length, so choosing between Search and Stitch relies on range_lens,
+// which counts a bounded number of elements and otherwise estimates from
+// the lengths of the sets. Splicing isn't used yet: its segments reach
+// to the next element of the small set or to the end of the large one,
+// and would need clamping to the range.
+
+pub fn difference_in<'a, T: Ord, R: RangeBounds<T>>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<T>,
+    range: R,
+) -> Difference<'a, T> {
+    let bounds = (range.start_bound(), range.end_bound());
+    let self_range = selve.range(bounds);
+    let other_range = other.range(bounds);
+    // Stream the elements of self_iter, passing empty ranges for the rest.
+    let stream = |self_iter: Range<'a, T>, empty: Range<'a, T>| Difference {
+        inner: DifferenceInner::Stitch {
+            self_prefix: self_iter,
+            self_iter: empty.clone(),
+            self_suffix: empty.clone(),
+            other_iter: Peeking::new(empty),
         },
+        lens: MergeLens::at_most(selve.len(), other.len()),
+        self_set: selve,
+        other_set: other,
+        marker: PhantomData,
+    };
+    let (self_min, self_max) = match (self_range.clone().next(), self_range.clone().next_back()) {
+        (Some(self_min), Some(self_max)) => (self_min, self_max),
+        _ => return stream(self_range.clone(), self_range),
+    };
+    let empty = selve.range(self_min..self_min);
+    let (other_min, other_max) = match (other_range.clone().next(), other_range.clone().next_back())
+    {
+        (Some(other_min), Some(other_max)) => (other_min, other_max),
+        _ => return stream(self_range, empty),
+    };
+    match (self_min.cmp(other_max), self_max.cmp(other_min)) {
+        (Greater, _) | (_, Less) => stream(self_range, empty),
+        (Equal, _) => {
+            let mut self_iter = self_range;
+            self_iter.next();
+            stream(self_iter, empty)
+        }
+        (_, Equal) => {
+            let mut self_iter = self_range;
+            self_iter.next_back();
+            stream(self_iter, empty)
+        }
+        _ => {
+            let other_iter = other.range(self_min..=self_max);
+            let (self_len, other_len) =
+                range_lens(self_range, other_iter.clone(), (selve.len(), other.len()));
+            let self_prefix = selve.range(self_min..max(self_min, other_min));
+            let self_iter = selve.range(max(self_min, other_min)..=min(self_max, other_max));
+            let self_suffix = if other_max < self_max {
+                selve.range((Excluded(other_max), Included(self_max)))
+            } else {
+                empty
+            };
+            let lens = MergeLens::of_ranges((self_len, other_len), (selve.len(), other.len()));
+            let inner = if self_len <= other_len / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+                DifferenceInner::Search {
+                    self_prefix,
+                    self_iter,
+                    self_suffix,
+                    other_set: other,
+                }
+            } else {
+                DifferenceInner::Stitch {
+                    self_prefix,
+                    self_iter,
+                    self_suffix,
+                    other_iter: Peeking::new(other_iter),
+                }
+            };
+            Difference {
+                inner,
+                lens,
+                self_set: selve,
+                other_set: other,
+                marker: PhantomData,
+            }
+        }
     }
 }
 
-pub fn symmdiff<'a, T: Ord>(
+pub fn intersection_in<'a, T: Ord, R: RangeBounds<T>>(
     selve: &'a BTreeSet<T>,
     other: &'a BTreeSet<T>,
+    range: R,
+) -> Intersection<'a, T> {
+    let bounds = (range.start_bound(), range.end_bound());
+    let self_range = selve.range(bounds);
+    let other_range = other.range(bounds);
+    let (self_min, self_max) = match (self_range.clone().next(), self_range.clone().next_back()) {
+        (Some(self_min), Some(self_max)) => (self_min, self_max),
+        _ => return Intersection::of_sets(IntersectionInner::Answer(None), selve, other),
+    };
+    let (other_min, other_max) = match (other_range.clone().next(), other_range.clone().next_back())
+    {
+        (Some(other_min), Some(other_max)) => (other_min, other_max),
+        _ => return Intersection::of_sets(IntersectionInner::Answer(None), selve, other),
+    };
+    let (self_len, other_len, inner) = match (self_min.cmp(other_max), self_max.cmp(other_min)) {
+        (Greater, _) | (_, Less) => (0, 0, IntersectionInner::Answer(None)),
+        (Equal, _) => (1, 1, IntersectionInner::Answer(Some((self_min, other_max)))),
+        (_, Equal) => (1, 1, IntersectionInner::Answer(Some((self_max, other_min)))),
+        _ => {
+            let overlap = max(self_min, other_min)..=min(self_max, other_max);
+            let (self_len, other_len) = range_lens(
+                selve.range(overlap.clone()),
+                other.range(overlap.clone()),
+                (selve.len(), other.len()),
+            );
+            let inner = if self_len <= other_len / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+                IntersectionInner::SearchOther {
+                    small_iter: selve.range(overlap),
+                    large_set: other,
+                }
+            } else if other_len <= self_len / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+                IntersectionInner::SearchSelf {
+                    small_iter: other.range(overlap),
+                    large_set: selve,
+                }
+            } else {
+                IntersectionInner::Stitch {
+                    a: selve.range(overlap.clone()),
+                    b: other.range(overlap),
+                }
+            };
+            (self_len, other_len, inner)
+        }
+    };
+    Intersection {
+        inner,
+        lens: MergeLens::of_ranges((self_len, other_len), (selve.len(), other.len())),
+        self_set: selve,
+        other_set: other,
+        marker: PhantomData,
+    }
+}
+
+pub fn is_disjoint_in<T: Ord, R: RangeBounds<T>>(
+    selve: &BTreeSet<T>,
+    other: &BTreeSet<T>,
+    range: R,
+) -> bool {
+    intersection_in(selve, other, range).next().is_none()
+}
+
+pub fn union_in<'a, T: Ord, R: RangeBounds<T>>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<T>,
+    range: R,
+) -> Union<'a, T> {
+    let bounds = (range.start_bound(), range.end_bound());
+    Union {
+        merge: MergeInner::Stitch(MergeIterInner::new_in(selve, other, bounds)),
+        lens: MergeLens::at_most(selve.len(), other.len()),
+        self_set: selve,
+        other_set: other,
+    }
+}
+
+// Operations resuming strictly after the last element seen, for pagination.
+// They pick a strategy like the operations on a range, so each page costs
+// a few searches and a bounded count on top of the elements it returns,
+// rather than a walk over the remainder of either side.
+
+pub fn difference_after<'a, T: Ord>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<T>,
+    last_seen: &T,
+) -> Difference<'a, T> {
+    difference_in(selve, other, (Excluded(last_seen), Unbounded))
+}
+
+pub fn intersection_after<'a, T: Ord>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<T>,
+    last_seen: &T,
+) -> Intersection<'a, T> {
+    intersection_in(selve, other, (Excluded(last_seen), Unbounded))
+}
+
+pub fn is_subset_after<T: Ord>(selve: &BTreeSet<T>, other: &BTreeSet<T>, last_seen: &T) -> bool {
+    difference_after(selve, other, last_seen).next().is_none()
+}
+
+pub fn symmdiff_after<'a, T: Ord>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<T>,
+    last_seen: &T,
 ) -> SymmetricDifference<'a, T> {
-    (selve as &dyn JustToIndentAsMuch<T>).symmetric_difference(other)
+    let bounds = (Excluded(last_seen), Unbounded);
+    SymmetricDifference {
+        merge: MergeInner::Stitch(MergeIterInner::new_in(selve, other, bounds)),
+        lens: MergeLens::at_most(selve.len(), other.len()),
+    }
+}
+
+pub fn union_after<'a, T: Ord>(
+    selve: &'a BTreeSet<T>,
+    other: &'a BTreeSet<T>,
+    last_seen: &T,
+) -> Union<'a, T> {
+    union_in(selve, other, (Excluded(last_seen), Unbounded))
+}
+
+/// Takes up to `limit` elements from the iterator, and returns them along
+/// with the cursor to pass to the next `*_after` call: the last element
+/// taken, or `None` if the iterator has no elements left.
+/// If `limit` is zero, the page is empty and the cursor is the first element,
+/// or `None` if there is none, so resuming from it skips that element.
+pub fn page<'a, T, I>(iter: I, limit: usize) -> (Vec<&'a T>, Option<&'a T>)
+where
+    I: Iterator<Item = &'a T>,
+{
+    let mut iter = iter.peekable();
+    if limit == 0 {
+        return (Vec::new(), iter.peek().copied());
+    }
+    let items: Vec<&'a T> = iter.by_ref().take(limit).collect();
+    let cursor = match iter.peek() {
+        Some(_) => items.last().copied(),
+        None => None,
+    };
+    (items, cursor)
+}
+
+/// Pairs of sorted views that difference, intersection and union accept:
+/// two BTreeSets, which get the strategies above, or any other two of
+/// a BTreeSet, a Range of one, a sorted slice and the keys of a BTreeMap,
+/// which get the operations on views below.
+pub trait SortedViews<'a, T: 'a, B> {
+    type Difference: Iterator<Item = &'a T>;
+    type Intersection: Iterator<Item = &'a T>;
+    type Union: Iterator<Item = &'a T>;
+
+    fn difference(self, other: B) -> Self::Difference;
+    fn intersection(self, other: B) -> Self::Intersection;
+    fn union(self, other: B) -> Self::Union;
+}
+
+impl<'a, T: Ord + 'a> SortedViews<'a, T, &'a BTreeSet<T>> for &'a BTreeSet<T> {
+    type Difference = Difference<'a, T>;
+    type Intersection = Intersection<'a, T>;
+    type Union = Union<'a, T>;
+
+    fn difference(self, other: &'a BTreeSet<T>) -> Self::Difference {
+        difference_borrowed(self, other)
+    }
+
+    fn intersection(self, other: &'a BTreeSet<T>) -> Self::Intersection {
+        intersection_borrowed(self, other)
+    }
+
+    fn union(self, other: &'a BTreeSet<T>) -> Self::Union {
+        JustToIndentAsMuch::union(self, other)
+    }
+}
+
+macro_rules! sorted_views {
+    ($(<$($param: ident),*> $a: ty, $b: ty;)+) => {
+        $(
+            impl<'a, T: Ord + 'a, $($param: 'a),*> SortedViews<'a, T, $b> for $a {
+                type Difference = ViewDifference<'a, T, $a, $b>;
+                type Intersection = ViewIntersection<'a, T, $a, $b>;
+                type Union = ViewUnion<'a, T, $a, $b>;
+
+                fn difference(self, other: $b) -> Self::Difference {
+                    view_difference(self, other)
+                }
+
+                fn intersection(self, other: $b) -> Self::Intersection {
+                    view_intersection(self, other)
+                }
+
+                fn union(self, other: $b) -> Self::Union {
+                    view_union(self, other)
+                }
+            }
+        )+
+    };
+}
+
+sorted_views! {
+    <> &'a BTreeSet<T>, Range<'a, T>;
+    <> &'a BTreeSet<T>, &'a [T];
+    <V> &'a BTreeSet<T>, &'a BTreeMap<T, V>;
+    <> Range<'a, T>, &'a BTreeSet<T>;
+    <> Range<'a, T>, Range<'a, T>;
+    <> Range<'a, T>, &'a [T];
+    <V> Range<'a, T>, &'a BTreeMap<T, V>;
+    <> &'a [T], &'a BTreeSet<T>;
+    <> &'a [T], Range<'a, T>;
+    <> &'a [T], &'a [T];
+    <V> &'a [T], &'a BTreeMap<T, V>;
+    <V> &'a BTreeMap<T, V>, &'a BTreeSet<T>;
+    <V> &'a BTreeMap<T, V>, Range<'a, T>;
+    <V> &'a BTreeMap<T, V>, &'a [T];
+    <V, W> &'a BTreeMap<T, V>, &'a BTreeMap<T, W>;
+}
+
+// Operations on any other sorted views, such as a BTreeSet and a sorted
+// slice. They search the large view when the views know their length and
+// the large view can search, and otherwise stitch, skipping elements below
+// the other view's minimum if the view can seek.
+
+fn view_difference<'a, T, A, B>(a: A, b: B) -> ViewDifference<'a, T, A, B>
+where
+    T: Ord + 'a,
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T>,
+{
+    let a_iter = a.iter();
+    let mut b_iter = b.iter();
+    if let Some(a_min) = a_iter.clone().next() {
+        if let (Some(a_len), Some(b_len)) = (a.len(), b.len()) {
+            if a_len <= b_len / ITER_PERFORMANCE_TIPPING_SIZE_DIFF && b.can_search() {
+                return ViewDifference {
+                    inner: ViewDifferenceInner::Search { a_iter, b },
+                };
+            }
+        }
+        b.seek(&mut b_iter, a_min);
+    }
+    ViewDifference {
+        inner: ViewDifferenceInner::Stitch {
+            a_iter,
+            b_iter: Peeking::new(b_iter),
+        },
+    }
+}
+
+fn view_intersection<'a, T, A, B>(a: A, b: B) -> ViewIntersection<'a, T, A, B>
+where
+    T: Ord + 'a,
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T>,
+{
+    let mut a_iter = a.iter();
+    let mut b_iter = b.iter();
+    if let (Some(a_min), Some(b_min)) = (a_iter.clone().next(), b_iter.clone().next()) {
+        if let (Some(a_len), Some(b_len)) = (a.len(), b.len()) {
+            if a_len <= b_len / ITER_PERFORMANCE_TIPPING_SIZE_DIFF && b.can_search() {
+                return ViewIntersection {
+                    inner: ViewIntersectionInner::SearchB { a_iter, b },
+                };
+            }
+            if b_len <= a_len / ITER_PERFORMANCE_TIPPING_SIZE_DIFF && a.can_search() {
+                return ViewIntersection {
+                    inner: ViewIntersectionInner::SearchA { b_iter, a },
+                };
+            }
+        }
+        if a_min < b_min {
+            a.seek(&mut a_iter, b_min);
+        } else {
+            b.seek(&mut b_iter, a_min);
+        }
+    }
+    ViewIntersection {
+        inner: ViewIntersectionInner::Stitch { a_iter, b_iter },
+    }
+}
+
+fn view_union<'a, T, A, B>(a: A, b: B) -> ViewUnion<'a, T, A, B>
+where
+    T: Ord + 'a,
+    A: SortedView<'a, T>,
+    B: SortedView<'a, T>,
+{
+    ViewUnion {
+        a_iter: Peeking::new(a.iter()),
+        b_iter: Peeking::new(b.iter()),
+    }
+}
+
+// Operations matching elements on a key projected from them, rather than on
+// the elements themselves, so the sets may even hold different types.
+// Each set's order must agree with the order of its keys, i.e. the key of
+// an element may not be greater than the key of any later element, as with
+// a key that is a leading field of the element. Keys need not be unique:
+// intersection_by_key pairs up all elements of a and b sharing a key.
+
+pub fn intersection_by_key<'a, A, B, K, FA, FB>(
+    a: &'a BTreeSet<A>,
+    b: &'a BTreeSet<B>,
+    key_a: FA,
+    key_b: FB,
+) -> IntersectionByKey<'a, A, B, K, FA, FB>
+where
+    K: Ord,
+    FA: FnMut(&A) -> K,
+    FB: FnMut(&B) -> K,
+{
+    let mut iter = IntersectionByKey {
+        a_iter: a.iter(),
+        a_head: None,
+        b_run: b.iter(),
+        b_pair: b.iter(),
+        key_a,
+        key_b,
+    };
+    iter.next_a();
+    iter
+}
+
+pub fn difference_by_key<'a, A, B, K, FA, FB>(
+    a: &'a BTreeSet<A>,
+    b: &'a BTreeSet<B>,
+    key_a: FA,
+    key_b: FB,
+) -> DifferenceByKey<'a, A, B, FA, FB>
+where
+    K: Ord,
+    FA: FnMut(&A) -> K,
+    FB: FnMut(&B) -> K,
+{
+    DifferenceByKey {
+        a_iter: a.iter(),
+        b_iter: Peeking::new(b.iter()),
+        key_a,
+        key_b,
+    }
+}
+
+// Owned results, as returned by the operators on `&BTreeSet`.
+
+pub fn bitand<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(intersection(selve, other).cloned())
+}
+
+pub fn bitor<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(union(selve, other).cloned())
+}
+
+pub fn bitxor<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(symmdiff(selve, other).cloned())
 }
 
-pub fn union<'a, T: Ord>(selve: &'a BTreeSet<T>, other: &'a BTreeSet<T>) -> Union<'a, T> {
-    (selve as &dyn JustToIndentAsMuch<T>).union(other)
+pub fn sub<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(difference(selve, other).cloned())
 }
@@ -0,0 +1,756 @@
+// Set operations on sorted slices without duplicates, as a baseline for BTreeSet.
+use core::cmp::Ordering::{Equal, Greater, Less};
+use core::cmp::{max, min};
+use core::iter::FusedIterator;
+
+/// Returns the index of the first element of the slice not below the value.
+/// Probes at exponentially growing distances from the start before
+/// searching in between, so it's cheap if that element is near the start.
+fn gallop<T: Ord>(slice: &[T], value: &T) -> usize {
+    let mut bound = 1;
+    while bound < slice.len() && slice[bound] < *value {
+        bound *= 2;
+    }
+    let lo = bound / 2;
+    let hi = min(bound + 1, slice.len());
+    lo + slice[lo..hi].partition_point(|elt| elt < value)
+}
+
+/// Core of SymmetricDifference and Union, and of Difference of a large set,
+/// merging similarly sized slices.
+#[derive(Clone, Debug)]
+struct MergeStitch<'a, T: 'a> {
+    a: &'a [T],
+    b: &'a [T],
+}
+
+impl<'a, T: Ord> MergeStitch<'a, T> {
+    /// The number of elements left on either side.
+    fn lens(&self) -> (usize, usize) {
+        (self.a.len(), self.b.len())
+    }
+
+    fn nexts(&mut self) -> (Option<&'a T>, Option<&'a T>) {
+        match (self.a.split_first(), self.b.split_first()) {
+            (Some((a1, a_rest)), Some((b1, b_rest))) => match a1.cmp(b1) {
+                Less => {
+                    self.a = a_rest;
+                    (Some(a1), None)
+                }
+                Equal => {
+                    self.a = a_rest;
+                    self.b = b_rest;
+                    (Some(a1), Some(b1))
+                }
+                Greater => {
+                    self.b = b_rest;
+                    (None, Some(b1))
+                }
+            },
+            (Some((a1, a_rest)), None) => {
+                self.a = a_rest;
+                (Some(a1), None)
+            }
+            (None, Some((b1, b_rest))) => {
+                self.b = b_rest;
+                (None, Some(b1))
+            }
+            (None, None) => (None, None),
+        }
+    }
+
+    /// Folds the pairs that nexts() would report, until both are exhausted.
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (Option<&'a T>, Option<&'a T>)) -> B,
+    {
+        let MergeStitch { mut a, mut b } = self;
+        let mut acc = init;
+        while let (Some((a1, a_rest)), Some((b1, b_rest))) = (a.split_first(), b.split_first()) {
+            match a1.cmp(b1) {
+                Less => {
+                    acc = f(acc, (Some(a1), None));
+                    a = a_rest;
+                }
+                Equal => {
+                    acc = f(acc, (Some(a1), Some(b1)));
+                    a = a_rest;
+                    b = b_rest;
+                }
+                Greater => {
+                    acc = f(acc, (None, Some(b1)));
+                    b = b_rest;
+                }
+            }
+        }
+        let acc = a.iter().fold(acc, |acc, a1| f(acc, (Some(a1), None)));
+        b.iter().fold(acc, |acc, b1| f(acc, (None, Some(b1))))
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum MergeSide {
+    A,
+    B,
+}
+
+impl MergeSide {
+    fn report<T>(self, small_next: Option<T>, large_next: Option<T>) -> (Option<T>, Option<T>) {
+        match self {
+            MergeSide::A => (small_next, large_next),
+            MergeSide::B => (large_next, small_next),
+        }
+    }
+}
+
+/// Alternative core for a small slice against a large slice.
+/// Walks the small slice, galloping through the large slice to find
+/// the segment in between each pair of elements, and streams the segments.
+#[derive(Clone, Debug)]
+struct MergeGallop<'a, T: 'a> {
+    small: &'a [T],
+    small_side: MergeSide,
+    small_next: Option<&'a T>, // upper bound of the current segment
+    large: &'a [T],
+    segment: &'a [T],
+}
+
+impl<'a, T: Ord> MergeGallop<'a, T> {
+    fn new(small: &'a [T], small_side: MergeSide, large: &'a [T]) -> Self {
+        let mut merge = MergeGallop {
+            small,
+            small_side,
+            small_next: None,
+            large,
+            segment: &large[..0],
+        };
+        merge.next_segment();
+        merge
+    }
+
+    // Moves on to the next element of the small slice,
+    // and the part of the large slice below it.
+    fn next_segment(&mut self) {
+        match self.small.split_first() {
+            Some((small_next, small_rest)) => {
+                let pos = gallop(self.large, small_next);
+                self.small = small_rest;
+                self.small_next = Some(small_next);
+                self.segment = &self.large[..pos];
+                self.large = &self.large[pos..];
+            }
+            None => {
+                self.small_next = None;
+                self.segment = self.large;
+                self.large = &self.large[self.large.len()..];
+            }
+        }
+    }
+
+    /// The number of elements left on either side.
+    fn lens(&self) -> (usize, usize) {
+        let small_len = self.small.len() + usize::from(self.small_next.is_some());
+        let large_len = self.segment.len() + self.large.len();
+        match self.small_side {
+            MergeSide::A => (small_len, large_len),
+            MergeSide::B => (large_len, small_len),
+        }
+    }
+
+    fn nexts(&mut self) -> (Option<&'a T>, Option<&'a T>) {
+        if let Some((large_next, segment_rest)) = self.segment.split_first() {
+            self.segment = segment_rest;
+            return self.small_side.report(None, Some(large_next));
+        }
+        let small_next = match self.small_next {
+            Some(small_next) => small_next,
+            None => return (None, None),
+        };
+        let large_match = match self.large.split_first() {
+            Some((large_next, large_rest)) if large_next == small_next => {
+                self.large = large_rest;
+                Some(large_next)
+            }
+            _ => None,
+        };
+        self.next_segment();
+        self.small_side.report(Some(small_next), large_match)
+    }
+
+    /// Folds the pairs that nexts() would report, streaming each segment at once.
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (Option<&'a T>, Option<&'a T>)) -> B,
+    {
+        let small_side = self.small_side;
+        let mut acc = self.segment.iter().fold(init, |acc, large_next| {
+            f(acc, small_side.report(None, Some(large_next)))
+        });
+        while let Some(small_next) = self.small_next {
+            let large_match = match self.large.split_first() {
+                Some((large_next, large_rest)) if large_next == small_next => {
+                    self.large = large_rest;
+                    Some(large_next)
+                }
+                _ => None,
+            };
+            acc = f(acc, small_side.report(Some(small_next), large_match));
+            self.next_segment();
+            acc = self.segment.iter().fold(acc, |acc, large_next| {
+                f(acc, small_side.report(None, Some(large_next)))
+            });
+        }
+        acc
+    }
+}
+
+/// Choice between the cores of SymmetricDifference and Union.
+#[derive(Clone, Debug)]
+enum MergeInner<'a, T: 'a> {
+    Stitch(MergeStitch<'a, T>),
+    Gallop(MergeGallop<'a, T>),
+}
+
+impl<'a, T: Ord> MergeInner<'a, T> {
+    fn new(a: &'a [T], b: &'a [T]) -> Self {
+        if a.len() <= b.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            MergeInner::Gallop(MergeGallop::new(a, MergeSide::A, b))
+        } else if b.len() <= a.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            MergeInner::Gallop(MergeGallop::new(b, MergeSide::B, a))
+        } else {
+            MergeInner::Stitch(MergeStitch { a, b })
+        }
+    }
+
+    fn lens(&self) -> (usize, usize) {
+        match self {
+            MergeInner::Stitch(merge) => merge.lens(),
+            MergeInner::Gallop(merge) => merge.lens(),
+        }
+    }
+
+    fn nexts(&mut self) -> (Option<&'a T>, Option<&'a T>) {
+        match self {
+            MergeInner::Stitch(merge) => merge.nexts(),
+            MergeInner::Gallop(merge) => merge.nexts(),
+        }
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, (Option<&'a T>, Option<&'a T>)) -> B,
+    {
+        match self {
+            MergeInner::Stitch(merge) => merge.fold(init, f),
+            MergeInner::Gallop(merge) => merge.fold(init, f),
+        }
+    }
+}
+
+/// A lazy iterator producing elements in the difference of sorted slices.
+#[derive(Clone, Debug)]
+pub struct Difference<'a, T: 'a> {
+    inner: DifferenceInner<'a, T>,
+}
+#[derive(Clone, Debug)]
+enum DifferenceInner<'a, T: 'a> {
+    Stitch(MergeStitch<'a, T>), // merge and report the elements of self only
+    Search {
+        // iterate the small self, binary searching the rest of other
+        self_rest: &'a [T],
+        other_rest: &'a [T],
+    },
+    Gallop {
+        // iterate the small self, galloping through other
+        self_rest: &'a [T],
+        other_rest: &'a [T],
+    },
+    Splice(MergeGallop<'a, T>), // stream self in between the elements of the small other
+}
+
+/// A lazy iterator producing elements in the symmetric difference of sorted slices.
+#[derive(Clone, Debug)]
+pub struct SymmetricDifference<'a, T: 'a> {
+    merge: MergeInner<'a, T>,
+}
+
+/// A lazy iterator producing elements in the intersection of sorted slices.
+#[derive(Clone, Debug)]
+pub struct Intersection<'a, T: 'a> {
+    inner: IntersectionInner<'a, T>,
+}
+#[derive(Clone, Debug)]
+enum IntersectionInner<'a, T: 'a> {
+    Stitch {
+        // iterate similarly sized slices jointly, spotting matches along the way
+        a: &'a [T],
+        b: &'a [T],
+    },
+    Search {
+        // iterate the small slice, binary searching the rest of the large slice
+        small: &'a [T],
+        small_side: MergeSide,
+        large: &'a [T],
+    },
+    Gallop {
+        // iterate the small slice, galloping through the large slice
+        small: &'a [T],
+        small_side: MergeSide,
+        large: &'a [T],
+    },
+}
+
+/// A lazy iterator producing elements in the union of sorted slices.
+#[derive(Clone, Debug)]
+pub struct Union<'a, T: 'a> {
+    merge: MergeInner<'a, T>,
+}
+
+// This constant is used by functions that compare two slices.
+// It estimates the relative size at which galloping performs better
+// than merging, like its namesake in the BTreeSet modules.
+const ITER_PERFORMANCE_TIPPING_SIZE_DIFF: usize = 16;
+
+// Likewise, the relative size at which a plain binary search in the rest of
+// the large slice performs better than galloping, because the elements of
+// the small slice are too far apart for galloping to pay off.
+const SEARCH_PERFORMANCE_TIPPING_SIZE_DIFF: usize = 256;
+
+impl<'a, T: Ord> Iterator for Difference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match &mut self.inner {
+            DifferenceInner::Stitch(merge) => loop {
+                match merge.nexts() {
+                    (Some(self_next), None) => return Some(self_next),
+                    (None, None) => return None,
+                    _ => (),
+                }
+            },
+            DifferenceInner::Search {
+                self_rest,
+                other_rest,
+            } => loop {
+                let (self_next, self_tail) = self_rest.split_first()?;
+                *self_rest = self_tail;
+                let pos = other_rest.partition_point(|elt| elt < self_next);
+                *other_rest = &other_rest[pos..];
+                if other_rest.first() != Some(self_next) {
+                    return Some(self_next);
+                }
+            },
+            DifferenceInner::Gallop {
+                self_rest,
+                other_rest,
+            } => loop {
+                let (self_next, self_tail) = self_rest.split_first()?;
+                *self_rest = self_tail;
+                *other_rest = &other_rest[gallop(other_rest, self_next)..];
+                if other_rest.first() != Some(self_next) {
+                    return Some(self_next);
+                }
+            },
+            DifferenceInner::Splice(merge) => loop {
+                match merge.nexts() {
+                    (Some(self_next), None) => return Some(self_next),
+                    (None, None) => return None,
+                    _ => (),
+                }
+            },
+        }
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self.inner {
+            DifferenceInner::Stitch(merge) => merge.fold(init, |acc, nexts| match nexts {
+                (Some(self_next), None) => f(acc, self_next),
+                _ => acc,
+            }),
+            DifferenceInner::Search {
+                self_rest,
+                mut other_rest,
+            } => self_rest.iter().fold(init, |acc, self_next| {
+                let pos = other_rest.partition_point(|elt| elt < self_next);
+                other_rest = &other_rest[pos..];
+                if other_rest.first() != Some(self_next) {
+                    f(acc, self_next)
+                } else {
+                    acc
+                }
+            }),
+            DifferenceInner::Gallop {
+                self_rest,
+                mut other_rest,
+            } => self_rest.iter().fold(init, |acc, self_next| {
+                other_rest = &other_rest[gallop(other_rest, self_next)..];
+                if other_rest.first() != Some(self_next) {
+                    f(acc, self_next)
+                } else {
+                    acc
+                }
+            }),
+            DifferenceInner::Splice(merge) => merge.fold(init, |acc, nexts| match nexts {
+                (Some(self_next), None) => f(acc, self_next),
+                _ => acc,
+            }),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            DifferenceInner::Stitch(merge) => {
+                let (self_len, other_len) = merge.lens();
+                (self_len.saturating_sub(other_len), Some(self_len))
+            }
+            DifferenceInner::Search {
+                self_rest,
+                other_rest,
+            }
+            | DifferenceInner::Gallop {
+                self_rest,
+                other_rest,
+            } => (
+                self_rest.len().saturating_sub(other_rest.len()),
+                Some(self_rest.len()),
+            ),
+            DifferenceInner::Splice(merge) => {
+                // The small other is always on side B.
+                let (self_len, other_len) = merge.lens();
+                (self_len.saturating_sub(other_len), Some(self_len))
+            }
+        }
+    }
+}
+
+impl<T: Ord> FusedIterator for Difference<'_, T> {}
+
+impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            let (a_next, b_next) = self.merge.nexts();
+            if a_next.and(b_next).is_none() {
+                return a_next.or(b_next);
+            }
+        }
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.merge.fold(init, |acc, nexts| match nexts {
+            (Some(next), None) | (None, Some(next)) => f(acc, next),
+            _ => acc,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each element present in both slices takes one from either side.
+        let (a_len, b_len) = self.merge.lens();
+        (a_len.abs_diff(b_len), Some(a_len + b_len))
+    }
+}
+
+impl<T: Ord> FusedIterator for SymmetricDifference<'_, T> {}
+
+impl<'a, T: Ord> Iterator for Intersection<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match &mut self.inner {
+            IntersectionInner::Stitch { a, b } => loop {
+                let (a_next, a_rest) = a.split_first()?;
+                let (b_next, b_rest) = b.split_first()?;
+                match a_next.cmp(b_next) {
+                    Less => *a = a_rest,
+                    Greater => *b = b_rest,
+                    Equal => {
+                        *a = a_rest;
+                        *b = b_rest;
+                        return Some(a_next);
+                    }
+                }
+            },
+            IntersectionInner::Search {
+                small,
+                small_side,
+                large,
+            } => loop {
+                let (small_next, small_rest) = small.split_first()?;
+                *small = small_rest;
+                *large = &large[large.partition_point(|elt| elt < small_next)..];
+                if let Some((large_next, large_rest)) = large.split_first() {
+                    if large_next == small_next {
+                        *large = large_rest;
+                        return small_side.report(Some(small_next), Some(large_next)).0;
+                    }
+                }
+            },
+            IntersectionInner::Gallop {
+                small,
+                small_side,
+                large,
+            } => loop {
+                let (small_next, small_rest) = small.split_first()?;
+                *small = small_rest;
+                *large = &large[gallop(large, small_next)..];
+                if let Some((large_next, large_rest)) = large.split_first() {
+                    if large_next == small_next {
+                        *large = large_rest;
+                        return small_side.report(Some(small_next), Some(large_next)).0;
+                    }
+                }
+            },
+        }
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self.inner {
+            IntersectionInner::Stitch { mut a, mut b } => {
+                let mut acc = init;
+                while let (Some((a_next, a_rest)), Some((b_next, b_rest))) =
+                    (a.split_first(), b.split_first())
+                {
+                    match a_next.cmp(b_next) {
+                        Less => a = a_rest,
+                        Greater => b = b_rest,
+                        Equal => {
+                            acc = f(acc, a_next);
+                            a = a_rest;
+                            b = b_rest;
+                        }
+                    }
+                }
+                acc
+            }
+            IntersectionInner::Search {
+                small,
+                small_side,
+                mut large,
+            } => small.iter().fold(init, |acc, small_next| {
+                large = &large[large.partition_point(|elt| elt < small_next)..];
+                match large.split_first() {
+                    Some((large_next, large_rest)) if large_next == small_next => {
+                        large = large_rest;
+                        match small_side {
+                            MergeSide::A => f(acc, small_next),
+                            MergeSide::B => f(acc, large_next),
+                        }
+                    }
+                    _ => acc,
+                }
+            }),
+            IntersectionInner::Gallop {
+                small,
+                small_side,
+                mut large,
+            } => small.iter().fold(init, |acc, small_next| {
+                large = &large[gallop(large, small_next)..];
+                match large.split_first() {
+                    Some((large_next, large_rest)) if large_next == small_next => {
+                        large = large_rest;
+                        match small_side {
+                            MergeSide::A => f(acc, small_next),
+                            MergeSide::B => f(acc, large_next),
+                        }
+                    }
+                    _ => acc,
+                }
+            }),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            IntersectionInner::Stitch { a, b } => (0, Some(min(a.len(), b.len()))),
+            IntersectionInner::Search { small, .. } | IntersectionInner::Gallop { small, .. } => {
+                (0, Some(small.len()))
+            }
+        }
+    }
+}
+
+impl<T: Ord> FusedIterator for Intersection<'_, T> {}
+
+impl<'a, T: Ord> Iterator for Union<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let (a_next, b_next) = self.merge.nexts();
+        a_next.or(b_next)
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.merge.fold(init, |acc, nexts| match nexts {
+            (Some(next), _) | (None, Some(next)) => f(acc, next),
+            (None, None) => acc,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_len, b_len) = self.merge.lens();
+        (max(a_len, b_len), Some(a_len + b_len))
+    }
+}
+
+impl<T: Ord> FusedIterator for Union<'_, T> {}
+
+// Restricts a slice to the elements within the range of another slice.
+fn overlap<'a, T: Ord>(slice: &'a [T], other: &[T]) -> &'a [T] {
+    match (other.first(), other.last()) {
+        (Some(other_min), Some(other_max)) => {
+            let start = slice.partition_point(|elt| elt < other_min);
+            let end = slice.partition_point(|elt| elt <= other_max);
+            &slice[start..end.max(start)]
+        }
+        _ => &slice[..0],
+    }
+}
+
+pub fn is_subset<T: Ord>(selve: &[T], other: &[T]) -> bool {
+    if selve.len() > other.len() {
+        return false;
+    }
+    let other = overlap(other, selve);
+    if selve.len() > other.len() {
+        return false; // some elements of self lie outside other's range
+    }
+    if selve.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+        let mut other_rest = other;
+        for next in selve {
+            other_rest = &other_rest[gallop(other_rest, next)..];
+            match other_rest.split_first() {
+                Some((other_next, other_tail)) if other_next == next => other_rest = other_tail,
+                _ => return false,
+            }
+        }
+        true
+    } else {
+        let mut other_iter = other.iter();
+        selve.iter().all(|next| loop {
+            match other_iter
+                .next()
+                .map_or(Less, |other_next| next.cmp(other_next))
+            {
+                Less => break false,
+                Equal => break true,
+                Greater => (),
+            }
+        })
+    }
+}
+
+pub fn difference<'a, T: Ord>(selve: &'a [T], other: &'a [T]) -> Difference<'a, T> {
+    let other = overlap(other, selve);
+    Difference {
+        inner: if selve.len() <= other.len() / SEARCH_PERFORMANCE_TIPPING_SIZE_DIFF {
+            DifferenceInner::Search {
+                self_rest: selve,
+                other_rest: other,
+            }
+        } else if selve.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            DifferenceInner::Gallop {
+                self_rest: selve,
+                other_rest: other,
+            }
+        } else if other.len() <= selve.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            DifferenceInner::Splice(MergeGallop::new(other, MergeSide::B, selve))
+        } else {
+            DifferenceInner::Stitch(MergeStitch { a: selve, b: other })
+        },
+    }
+}
+
+pub fn intersection<'a, T: Ord>(selve: &'a [T], other: &'a [T]) -> Intersection<'a, T> {
+    let (a, b) = (overlap(selve, other), overlap(other, selve));
+    let (small, small_side, large) = if a.len() <= b.len() {
+        (a, MergeSide::A, b)
+    } else {
+        (b, MergeSide::B, a)
+    };
+    Intersection {
+        inner: if small.len() <= large.len() / SEARCH_PERFORMANCE_TIPPING_SIZE_DIFF {
+            IntersectionInner::Search {
+                small,
+                small_side,
+                large,
+            }
+        } else if small.len() <= large.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            IntersectionInner::Gallop {
+                small,
+                small_side,
+                large,
+            }
+        } else {
+            IntersectionInner::Stitch { a, b }
+        },
+    }
+}
+
+pub fn intersection_gallop<'a, T: Ord>(small: &'a [T], large: &'a [T]) -> Intersection<'a, T> {
+    Intersection {
+        inner: IntersectionInner::Gallop {
+            small,
+            small_side: MergeSide::A,
+            large,
+        },
+    }
+}
+
+pub fn intersection_search<'a, T: Ord>(small: &'a [T], large: &'a [T]) -> Intersection<'a, T> {
+    Intersection {
+        inner: IntersectionInner::Search {
+            small,
+            small_side: MergeSide::A,
+            large,
+        },
+    }
+}
+
+pub fn intersection_stitch<'a, T: Ord>(a: &'a [T], b: &'a [T]) -> Intersection<'a, T> {
+    Intersection {
+        inner: IntersectionInner::Stitch { a, b },
+    }
+}
+
+pub fn symmdiff<'a, T: Ord>(selve: &'a [T], other: &'a [T]) -> SymmetricDifference<'a, T> {
+    SymmetricDifference {
+        merge: MergeInner::new(selve, other),
+    }
+}
+
+pub fn union<'a, T: Ord>(selve: &'a [T], other: &'a [T]) -> Union<'a, T> {
+    Union {
+        merge: MergeInner::new(selve, other),
+    }
+}
+
+// Owned results, the counterpart of the operators on `&BTreeSet`.
+
+pub fn bitand<T: Ord + Clone>(selve: &[T], other: &[T]) -> Vec<T> {
+    intersection(selve, other).cloned().collect()
+}
+
+pub fn bitor<T: Ord + Clone>(selve: &[T], other: &[T]) -> Vec<T> {
+    union(selve, other).cloned().collect()
+}
+
+pub fn bitxor<T: Ord + Clone>(selve: &[T], other: &[T]) -> Vec<T> {
+    symmdiff(selve, other).cloned().collect()
+}
+
+pub fn sub<T: Ord + Clone>(selve: &[T], other: &[T]) -> Vec<T> {
+    difference(selve, other).cloned().collect()
+}
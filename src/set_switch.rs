@@ -6,6 +6,8 @@ use core::iter::{FusedIterator, Peekable};
 use std::collections::btree_set::Iter;
 use std::collections::BTreeSet;
 
+use crate::sorted_view::from_sorted;
+
 /*
 // This is pretty much entirely stolen from TreeSet, since BTreeMap has an identical interface
 // to TreeMap
@@ -171,6 +173,53 @@ where
         (a_next, b_next)
     }
 
+    /// Folds the pairs that nexts() would report, until both are exhausted,
+    /// keeping the next element of each side at hand instead of in `peeked`.
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (Option<I::Item>, Option<I::Item>)) -> B,
+    {
+        let MergeIterInner {
+            mut a,
+            mut b,
+            peeked,
+        } = self;
+        let (mut a_next, mut b_next) = match peeked {
+            Some(MergeIterPeeked::A(next)) => (Some(next), b.next()),
+            Some(MergeIterPeeked::B(next)) => (a.next(), Some(next)),
+            None => (a.next(), b.next()),
+        };
+        let mut acc = init;
+        loop {
+            match (a_next, b_next) {
+                (Some(a1), Some(b1)) => match a1.cmp(&b1) {
+                    Less => {
+                        acc = f(acc, (a_next, None));
+                        a_next = a.next();
+                    }
+                    Equal => {
+                        acc = f(acc, (a_next, b_next));
+                        a_next = a.next();
+                        b_next = b.next();
+                    }
+                    Greater => {
+                        acc = f(acc, (None, b_next));
+                        b_next = b.next();
+                    }
+                },
+                (Some(_), None) => {
+                    acc = f(acc, (a_next, None));
+                    return a.fold(acc, |acc, a1| f(acc, (Some(a1), None)));
+                }
+                (None, Some(_)) => {
+                    acc = f(acc, (None, b_next));
+                    return b.fold(acc, |acc, b1| f(acc, (None, Some(b1))));
+                }
+                (None, None) => return acc,
+            }
+        }
+    }
+
     fn lens(&self) -> (usize, usize) {
         match self.peeked {
             Some(MergeIterPeeked::A(_)) => (1 + self.a.len(), self.b.len()),
@@ -206,13 +255,10 @@ pub struct Difference<'a, T: 'a> {
 #[derive(Debug)]
 enum DifferenceInner<'a, T: 'a> {
     Stitch {
-        // iterate all of self and some of other, spotting matches along the way
+        // iterate all of self and some of other, spotting matches along the way,
+        // or iterate self and look up in other
         self_iter: Iter<'a, T>,
         other_iter: Peekable<Iter<'a, T>>,
-    },
-    Search {
-        // iterate a small set, look up in the large set
-        self_iter: Iter<'a, T>,
         other_set: &'a BTreeSet<T>,
     },
     Iterate(Iter<'a, T>), // simply stream self's elements
@@ -399,15 +445,10 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
                     self_iter.next_back();
                     DifferenceInner::Iterate(self_iter)
                 }
-                _ if self.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF => {
-                    DifferenceInner::Search {
-                        self_iter: self.iter(),
-                        other_set: other,
-                    }
-                }
                 _ => DifferenceInner::Stitch {
                     self_iter: self.iter(),
                     other_iter: other.iter().peekable(),
+                    other_set: other,
                 },
             },
         }
@@ -668,27 +709,30 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
             }
             Less => (),
         }
-        if self_iter.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
-            // Big difference in number of elements.
-            for next in self_iter {
-                if !other.contains(next) {
-                    return false;
-                }
-            }
-        } else {
-            // Self is not much smaller than other set.
-            let mut other_iter = other.iter();
-            other_iter.next();
-            other_iter.next_back();
-            let mut self_next = self_iter.next();
-            while let Some(self1) = self_next {
+        let mut other_iter = other.iter();
+        other_iter.next();
+        other_iter.next_back();
+        // Stitch while the remainder of self is not much smaller than
+        // the remainder of other, and switch to searching once it is.
+        while self_iter.len() > other_iter.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+            let self1 = match self_iter.next() {
+                Some(self1) => self1,
+                None => return true,
+            };
+            loop {
                 match other_iter.next().map_or(Less, |other1| self1.cmp(other1)) {
                     Less => return false,
-                    Equal => self_next = self_iter.next(),
+                    Equal => break,
                     Greater => (),
                 }
             }
         }
+        // Big difference in number of elements remaining.
+        for next in self_iter {
+            if !other.contains(next) {
+                return false;
+            }
+        }
         true
     }
 
@@ -1206,6 +1250,12 @@ impl<T> FusedIterator for Range<'_, T> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
 */
+/// Outcome of an attempt to finish an iteration step by searching.
+enum Search<'a, T> {
+    IsNotWorthIt,
+    Found(Option<&'a T>),
+}
+
 impl<T> Clone for Difference<'_, T> {
     fn clone(&self) -> Self {
         Difference {
@@ -1213,15 +1263,10 @@ impl<T> Clone for Difference<'_, T> {
                 DifferenceInner::Stitch {
                     self_iter,
                     other_iter,
+                    other_set,
                 } => DifferenceInner::Stitch {
                     self_iter: self_iter.clone(),
                     other_iter: other_iter.clone(),
-                },
-                DifferenceInner::Search {
-                    self_iter,
-                    other_set,
-                } => DifferenceInner::Search {
-                    self_iter: self_iter.clone(),
                     other_set,
                 },
                 DifferenceInner::Iterate(iter) => DifferenceInner::Iterate(iter.clone()),
@@ -1233,11 +1278,37 @@ impl<'a, T: Ord> Iterator for Difference<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
+        fn search_remainder<'b, S: Ord>(
+            self_iter: &mut Iter<'b, S>,
+            other_len: usize,
+            other_set: &BTreeSet<S>,
+        ) -> Search<'b, S> {
+            if self_iter.len() > other_len / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+                Search::IsNotWorthIt
+            } else {
+                // At this point, other_iter remains stuck, but it won't be
+                // used anymore. Its length remains large, so we will keep
+                // coming back here, and it won't spoil size_hint.
+                for next in self_iter {
+                    if !other_set.contains(next) {
+                        return Search::Found(Some(next));
+                    }
+                }
+                Search::Found(None)
+            }
+        }
+
         match &mut self.inner {
             DifferenceInner::Stitch {
                 self_iter,
                 other_iter,
+                other_set,
             } => {
+                if let Search::Found(result) =
+                    search_remainder(self_iter, other_iter.len(), other_set)
+                {
+                    return result;
+                }
                 let mut self_next = self_iter.next()?;
                 loop {
                     match other_iter
@@ -1246,8 +1317,13 @@ impl<'a, T: Ord> Iterator for Difference<'a, T> {
                     {
                         Less => return Some(self_next),
                         Equal => {
-                            self_next = self_iter.next()?;
                             other_iter.next();
+                            if let Search::Found(result) =
+                                search_remainder(self_iter, other_iter.len(), other_set)
+                            {
+                                return result;
+                            }
+                            self_next = self_iter.next()?;
                         }
                         Greater => {
                             other_iter.next();
@@ -1255,16 +1331,52 @@ impl<'a, T: Ord> Iterator for Difference<'a, T> {
                     }
                 }
             }
-            DifferenceInner::Search {
-                self_iter,
+            DifferenceInner::Iterate(iter) => iter.next(),
+        }
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self.inner {
+            DifferenceInner::Stitch {
+                mut self_iter,
+                mut other_iter,
                 other_set,
-            } => loop {
-                let self_next = self_iter.next()?;
-                if !other_set.contains(&self_next) {
-                    return Some(self_next);
+            } => {
+                let mut acc = init;
+                loop {
+                    if self_iter.len() <= other_iter.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+                        return self_iter
+                            .filter(|self_next| !other_set.contains(self_next))
+                            .fold(acc, f);
+                    }
+                    let self_next = match self_iter.next() {
+                        Some(next) => next,
+                        None => return acc,
+                    };
+                    loop {
+                        match other_iter
+                            .peek()
+                            .map_or(Less, |other_next| self_next.cmp(other_next))
+                        {
+                            Less => {
+                                acc = f(acc, self_next);
+                                break;
+                            }
+                            Equal => {
+                                other_iter.next();
+                                break;
+                            }
+                            Greater => {
+                                other_iter.next();
+                            }
+                        }
+                    }
                 }
-            },
-            DifferenceInner::Iterate(iter) => iter.next(),
+            }
+            DifferenceInner::Iterate(iter) => iter.fold(init, f),
         }
     }
 
@@ -1273,11 +1385,8 @@ impl<'a, T: Ord> Iterator for Difference<'a, T> {
             DifferenceInner::Stitch {
                 self_iter,
                 other_iter,
+                ..
             } => (self_iter.len(), other_iter.len()),
-            DifferenceInner::Search {
-                self_iter,
-                other_set,
-            } => (self_iter.len(), other_set.len()),
             DifferenceInner::Iterate(iter) => (iter.len(), 0),
         };
         (self_len.saturating_sub(other_len), Some(self_len))
@@ -1303,12 +1412,23 @@ impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
         }
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.0.fold(init, |acc, nexts| match nexts {
+            (Some(next), None) | (None, Some(next)) => f(acc, next),
+            _ => acc,
+        })
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (a_len, b_len) = self.0.lens();
         // No checked_add, because even if a and b refer to the same set,
         // and T is an empty type, the storage overhead of sets limits
         // the number of elements to less than half the range of usize.
-        (0, Some(a_len + b_len))
+        // Each element present in both sets takes one from either side.
+        (a_len.abs_diff(b_len), Some(a_len + b_len))
     }
 }
 
@@ -1338,10 +1458,6 @@ impl<'a, T: Ord> Iterator for Intersection<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
-        enum Search<'a, T> {
-            IsNotWorthIt,
-            Found(Option<&'a T>),
-        }
         fn search_remainder<'b, S: Ord>(
             small_iter: &mut Iter<'b, S>,
             large_iter: &Iter<'b, S>,
@@ -1355,7 +1471,7 @@ impl<'a, T: Ord> Iterator for Intersection<'a, T> {
                 // be used anymore. large_iter's length remains large, so we
                 // will keep coming back here, and it won't spoil size_hint.
                 for next in small_iter {
-                    if large_set.contains(&next) {
+                    if large_set.contains(next) {
                         return Search::Found(Some(next));
                     }
                 }
@@ -1400,6 +1516,67 @@ impl<'a, T: Ord> Iterator for Intersection<'a, T> {
         }
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self.inner {
+            IntersectionInner::Stitch {
+                a_set,
+                b_set,
+                mut a_iter,
+                mut b_iter,
+            } => {
+                let mut acc = init;
+                loop {
+                    if a_iter.len() <= b_iter.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+                        return a_iter.filter(|a_next| b_set.contains(a_next)).fold(acc, f);
+                    }
+                    if b_iter.len() <= a_iter.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF {
+                        return b_iter.filter(|b_next| a_set.contains(b_next)).fold(acc, f);
+                    }
+                    let (mut a_next, mut b_next) = match (a_iter.next(), b_iter.next()) {
+                        (Some(a_next), Some(b_next)) => (a_next, b_next),
+                        _ => return acc,
+                    };
+                    loop {
+                        match a_next.cmp(b_next) {
+                            Less => {
+                                if a_iter.len() <= b_iter.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF
+                                {
+                                    return a_iter
+                                        .filter(|a_next| b_set.contains(a_next))
+                                        .fold(acc, f);
+                                }
+                                a_next = match a_iter.next() {
+                                    Some(next) => next,
+                                    None => return acc,
+                                };
+                            }
+                            Greater => {
+                                if b_iter.len() <= a_iter.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF
+                                {
+                                    return b_iter
+                                        .filter(|b_next| a_set.contains(b_next))
+                                        .fold(acc, f);
+                                }
+                                b_next = match b_iter.next() {
+                                    Some(next) => next,
+                                    None => return acc,
+                                };
+                            }
+                            Equal => {
+                                acc = f(acc, a_next);
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            IntersectionInner::Answer(answer) => answer.into_iter().fold(init, f),
+        }
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         match &self.inner {
             IntersectionInner::Stitch { a_iter, b_iter, .. } => {
@@ -1426,6 +1603,16 @@ impl<'a, T: Ord> Iterator for Union<'a, T> {
         a_next.or(b_next)
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.0.fold(init, |acc, nexts| match nexts {
+            (Some(next), _) | (None, Some(next)) => f(acc, next),
+            (None, None) => acc,
+        })
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (a_len, b_len) = self.0.lens();
         // No checked_add - see SymmetricDifference::size_hint.
@@ -1460,3 +1647,21 @@ pub fn symmdiff<'a, T: Ord>(
 pub fn union<'a, T: Ord>(selve: &'a BTreeSet<T>, other: &'a BTreeSet<T>) -> Union<'a, T> {
     (selve as &dyn JustToIndentAsMuch<T>).union(other)
 }
+
+// Owned results, as returned by the operators on `&BTreeSet`.
+
+pub fn bitand<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(intersection(selve, other).cloned())
+}
+
+pub fn bitor<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(union(selve, other).cloned())
+}
+
+pub fn bitxor<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(symmdiff(selve, other).cloned())
+}
+
+pub fn sub<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(difference(selve, other).cloned())
+}
@@ -6,6 +6,8 @@ use core::iter::{FusedIterator, Peekable};
 use std::collections::btree_set::{Iter, Range};
 use std::collections::BTreeSet;
 
+use crate::sorted_view::from_sorted;
+
 /*
 // This is pretty much entirely stolen from TreeSet, since BTreeMap has an identical interface
 // to TreeMap
@@ -141,7 +143,7 @@ enum MergeIterPeeked<I: Iterator> {
 
 impl<I> MergeIterInner<I>
 where
-    I: ExactSizeIterator + FusedIterator,
+    I: FusedIterator,
     I::Item: Copy + Ord,
 {
     fn new(a: I, b: I) -> Self {
@@ -171,6 +173,59 @@ where
         (a_next, b_next)
     }
 
+    /// Folds the pairs that nexts() would report, until both are exhausted,
+    /// keeping the next element of each side at hand instead of in `peeked`.
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, (Option<I::Item>, Option<I::Item>)) -> B,
+    {
+        let MergeIterInner {
+            mut a,
+            mut b,
+            peeked,
+        } = self;
+        let (mut a_next, mut b_next) = match peeked {
+            Some(MergeIterPeeked::A(next)) => (Some(next), b.next()),
+            Some(MergeIterPeeked::B(next)) => (a.next(), Some(next)),
+            None => (a.next(), b.next()),
+        };
+        let mut acc = init;
+        loop {
+            match (a_next, b_next) {
+                (Some(a1), Some(b1)) => match a1.cmp(&b1) {
+                    Less => {
+                        acc = f(acc, (a_next, None));
+                        a_next = a.next();
+                    }
+                    Equal => {
+                        acc = f(acc, (a_next, b_next));
+                        a_next = a.next();
+                        b_next = b.next();
+                    }
+                    Greater => {
+                        acc = f(acc, (None, b_next));
+                        b_next = b.next();
+                    }
+                },
+                (Some(_), None) => {
+                    acc = f(acc, (a_next, None));
+                    return a.fold(acc, |acc, a1| f(acc, (Some(a1), None)));
+                }
+                (None, Some(_)) => {
+                    acc = f(acc, (None, b_next));
+                    return b.fold(acc, |acc, b1| f(acc, (None, Some(b1))));
+                }
+                (None, None) => return acc,
+            }
+        }
+    }
+}
+
+impl<I> MergeIterInner<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Copy,
+{
     fn lens(&self) -> (usize, usize) {
         match self.peeked {
             Some(MergeIterPeeked::A(_)) => (1 + self.a.len(), self.b.len()),
@@ -180,6 +235,52 @@ where
     }
 }
 
+impl<'a, T: Ord> MergeIterInner<Range<'a, T>> {
+    /// Skips the elements below the bound, given the sets merged.
+    fn advance_to(&mut self, a_set: &'a BTreeSet<T>, b_set: &'a BTreeSet<T>, bound: &T) {
+        advance_range(a_set, &mut self.a, bound);
+        advance_range(b_set, &mut self.b, bound);
+        if let Some(MergeIterPeeked::A(next) | MergeIterPeeked::B(next)) = self.peeked {
+            if next < bound {
+                self.peeked = None;
+            }
+        }
+    }
+}
+
+/// Repositions a range into a set to start at the first element not below
+/// the bound, unless it already does, while keeping its upper end.
+fn advance_range<'a, T: Ord>(set: &'a BTreeSet<T>, range: &mut Range<'a, T>, bound: &T) {
+    if let (Some(first), Some(last)) = (range.clone().next(), range.clone().next_back()) {
+        if first < bound {
+            *range = if bound <= last {
+                set.range(bound..=last)
+            } else {
+                set.range(last..last)
+            };
+        }
+    }
+}
+
+/// Like advance_range, for a range read ahead.
+fn advance_peekable<'a, T: Ord>(
+    set: &'a BTreeSet<T>,
+    iter: &mut Peekable<Range<'a, T>>,
+    bound: &T,
+) {
+    let first = iter.peek().copied();
+    if let (Some(first), Some(last)) = (first, iter.clone().next_back()) {
+        if first < bound {
+            *iter = if bound <= last {
+                set.range(bound..=last)
+            } else {
+                set.range(last..last)
+            }
+            .peekable();
+        }
+    }
+}
+
 impl<I> Debug for MergeIterInner<I>
 where
     I: Iterator + Debug,
@@ -202,20 +303,25 @@ where
 /// [`difference`]: struct.BTreeSet.html#method.difference
 pub struct Difference<'a, T: 'a> {
     inner: DifferenceInner<'a, T>,
+    self_set: &'a BTreeSet<T>,
+    other_set: &'a BTreeSet<T>,
+    // bounds on the number of elements left, since ranges don't know
+    min_len: usize,
+    max_len: usize,
 }
 #[derive(Debug)]
 enum DifferenceInner<'a, T: 'a> {
     Stitch {
         // iterate all of self and some of other, spotting matches along the way
-        self_iter: Iter<'a, T>,
-        other_iter: Peekable<Iter<'a, T>>,
+        self_iter: Range<'a, T>,
+        other_iter: Peekable<Range<'a, T>>,
     },
     Search {
         // iterate a small set, look up in the large set
-        self_iter: Iter<'a, T>,
+        self_iter: Range<'a, T>,
         other_set: &'a BTreeSet<T>,
     },
-    Iterate(Iter<'a, T>), // simply stream self's elements
+    Iterate(Range<'a, T>), // simply stream self's elements
 }
 
 impl<T: fmt::Debug> fmt::Debug for Difference<'_, T> {
@@ -251,6 +357,11 @@ pub struct Intersection<'a, T: 'a> {
     b_range: Range<'a, T>,
     a_set: &'a BTreeSet<T>,
     b_set: &'a BTreeSet<T>,
+    // number of non-matching steps after which we consider re-seeking,
+    // adapted to how productive re-seeking turned out to be
+    next_count_max: usize,
+    // upper bound on the number of elements left, since ranges don't know
+    max_len: usize,
 }
 
 impl<T: fmt::Debug> fmt::Debug for Intersection<'_, T> {
@@ -258,6 +369,7 @@ impl<T: fmt::Debug> fmt::Debug for Intersection<'_, T> {
         f.debug_tuple("Intersection")
             .field(&self.a_range)
             .field(&self.b_range)
+            .field(&self.next_count_max)
             .finish()
     }
 }
@@ -269,11 +381,18 @@ impl<T: fmt::Debug> fmt::Debug for Intersection<'_, T> {
 ///
 /// [`BTreeSet`]: struct.BTreeSet.html
 /// [`union`]: struct.BTreeSet.html#method.union
-pub struct Union<'a, T: 'a>(MergeIterInner<Iter<'a, T>>);
+pub struct Union<'a, T: 'a> {
+    merge: MergeIterInner<Range<'a, T>>,
+    a_set: &'a BTreeSet<T>,
+    b_set: &'a BTreeSet<T>,
+    // bounds on the number of elements left, since ranges don't know
+    min_len: usize,
+    max_len: usize,
+}
 
 impl<T: fmt::Debug> fmt::Debug for Union<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("Union").field(&self.0).finish()
+        f.debug_tuple("Union").field(&self.merge).finish()
     }
 }
 
@@ -363,48 +482,56 @@ trait JustToIndentAsMuch<T> {
 }
 impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
     fn difference<'a>(&'a self, other: &'a BTreeSet<T>) -> Difference<'a, T> {
+        let stream = |self_iter: Range<'a, T>, len: usize| Difference {
+            inner: DifferenceInner::Iterate(self_iter),
+            self_set: self,
+            other_set: other,
+            min_len: len,
+            max_len: len,
+        };
         let (self_min, self_max) = if let (Some(self_min), Some(self_max)) =
             (self.iter().next(), self.iter().next_back())
         {
             (self_min, self_max)
         } else {
-            return Difference {
-                inner: DifferenceInner::Iterate(self.iter()),
-            };
+            return stream(self.range(..), self.len());
         };
         let (other_min, other_max) = if let (Some(other_min), Some(other_max)) =
             (other.iter().next(), other.iter().next_back())
         {
             (other_min, other_max)
         } else {
-            return Difference {
-                inner: DifferenceInner::Iterate(self.iter()),
-            };
+            return stream(self.range(..), self.len());
         };
-        Difference {
-            inner: match (self_min.cmp(other_max), self_max.cmp(other_min)) {
-                (Greater, _) | (_, Less) => DifferenceInner::Iterate(self.iter()),
-                (Equal, _) => {
-                    let mut self_iter = self.iter();
-                    self_iter.next();
-                    DifferenceInner::Iterate(self_iter)
-                }
-                (_, Equal) => {
-                    let mut self_iter = self.iter();
-                    self_iter.next_back();
-                    DifferenceInner::Iterate(self_iter)
-                }
-                _ if self.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF => {
-                    DifferenceInner::Search {
-                        self_iter: self.iter(),
-                        other_set: other,
-                    }
+        let inner = match (self_min.cmp(other_max), self_max.cmp(other_min)) {
+            (Greater, _) | (_, Less) => return stream(self.range(..), self.len()),
+            (Equal, _) => {
+                let mut self_iter = self.range(..);
+                self_iter.next();
+                return stream(self_iter, self.len() - 1);
+            }
+            (_, Equal) => {
+                let mut self_iter = self.range(..);
+                self_iter.next_back();
+                return stream(self_iter, self.len() - 1);
+            }
+            _ if self.len() <= other.len() / ITER_PERFORMANCE_TIPPING_SIZE_DIFF => {
+                DifferenceInner::Search {
+                    self_iter: self.range(..),
+                    other_set: other,
                 }
-                _ => DifferenceInner::Stitch {
-                    self_iter: self.iter(),
-                    other_iter: other.iter().peekable(),
-                },
+            }
+            _ => DifferenceInner::Stitch {
+                self_iter: self.range(..),
+                other_iter: other.range(..).peekable(),
             },
+        };
+        Difference {
+            inner,
+            self_set: self,
+            other_set: other,
+            min_len: self.len().saturating_sub(other.len()),
+            max_len: self.len(),
         }
     }
 
@@ -465,9 +592,11 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
     fn intersection<'a>(&'a self, other: &'a BTreeSet<T>) -> Intersection<'a, T> {
         Intersection {
             a_range: self.range(..),
-            a_set: &self,
+            a_set: self,
             b_range: other.range(..),
-            b_set: &other,
+            b_set: other,
+            next_count_max: ITER_PERFORMANCE_TIPPING_SIZE_DIFF,
+            max_len: min(self.len(), other.len()),
         }
     }
 
@@ -494,7 +623,14 @@ impl<T: Ord> JustToIndentAsMuch<T> for BTreeSet<T> {
     pub fn union<'a>(&'a self, other: &'a BTreeSet<T>) -> Union<'a, T> {
     */
     fn union<'a>(&'a self, other: &'a BTreeSet<T>) -> Union<'a, T> {
-        Union(MergeIterInner::new(self.iter(), other.iter()))
+        Union {
+            merge: MergeIterInner::new(self.range(..), other.range(..)),
+            a_set: self,
+            b_set: other,
+            min_len: max(self.len(), other.len()),
+            // No checked_add - see SymmetricDifference::size_hint.
+            max_len: self.len() + other.len(),
+        }
     }
 
     /*
@@ -1196,14 +1332,16 @@ impl<T> Clone for Difference<'_, T> {
                 },
                 DifferenceInner::Iterate(iter) => DifferenceInner::Iterate(iter.clone()),
             },
+            self_set: self.self_set,
+            other_set: self.other_set,
+            min_len: self.min_len,
+            max_len: self.max_len,
         }
     }
 }
-impl<'a, T: Ord> Iterator for Difference<'a, T> {
-    type Item = &'a T;
-
+impl<'a, T: Ord> DifferenceInner<'a, T> {
     fn next(&mut self) -> Option<&'a T> {
-        match &mut self.inner {
+        match self {
             DifferenceInner::Stitch {
                 self_iter,
                 other_iter,
@@ -1230,7 +1368,7 @@ impl<'a, T: Ord> Iterator for Difference<'a, T> {
                 other_set,
             } => loop {
                 let self_next = self_iter.next()?;
-                if !other_set.contains(&self_next) {
+                if !other_set.contains(self_next) {
                     return Some(self_next);
                 }
             },
@@ -1238,19 +1376,85 @@ impl<'a, T: Ord> Iterator for Difference<'a, T> {
         }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let (self_len, other_len) = match &self.inner {
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &'a T) -> B,
+    {
+        match self {
             DifferenceInner::Stitch {
                 self_iter,
-                other_iter,
-            } => (self_iter.len(), other_iter.len()),
+                mut other_iter,
+            } => self_iter.fold(init, |acc, self_next| loop {
+                match other_iter
+                    .peek()
+                    .map_or(Less, |other_next| self_next.cmp(other_next))
+                {
+                    Less => return f(acc, self_next),
+                    Equal => {
+                        other_iter.next();
+                        return acc;
+                    }
+                    Greater => {
+                        other_iter.next();
+                    }
+                }
+            }),
             DifferenceInner::Search {
                 self_iter,
                 other_set,
-            } => (self_iter.len(), other_set.len()),
-            DifferenceInner::Iterate(iter) => (iter.len(), 0),
-        };
-        (self_len.saturating_sub(other_len), Some(self_len))
+            } => self_iter
+                .filter(|self_next| !other_set.contains(self_next))
+                .fold(init, f),
+            DifferenceInner::Iterate(iter) => iter.fold(init, f),
+        }
+    }
+}
+
+impl<'a, T: Ord> Difference<'a, T> {
+    /// Skips the elements below the bound, so that the next element returned
+    /// is the first one not below the bound. Repositions the underlying
+    /// iterators by searching the sets, rather than by stepping through them.
+    /// Does nothing if the iteration already went past the bound.
+    pub fn advance_to(&mut self, bound: &T) {
+        match &mut self.inner {
+            DifferenceInner::Stitch {
+                self_iter,
+                other_iter,
+            } => {
+                advance_range(self.self_set, self_iter, bound);
+                advance_peekable(self.other_set, other_iter, bound);
+            }
+            DifferenceInner::Search { self_iter, .. } => {
+                advance_range(self.self_set, self_iter, bound)
+            }
+            DifferenceInner::Iterate(iter) => advance_range(self.self_set, iter, bound),
+        }
+        // We don't know how many elements we skipped.
+        self.min_len = 0;
+    }
+}
+
+impl<'a, T: Ord> Iterator for Difference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let next = self.inner.next();
+        if next.is_some() {
+            self.min_len = self.min_len.saturating_sub(1);
+            self.max_len -= 1;
+        }
+        next
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.inner.fold(init, f)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.min_len, Some(self.max_len))
     }
 }
 
@@ -1273,12 +1477,23 @@ impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
         }
     }
 
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.0.fold(init, |acc, nexts| match nexts {
+            (Some(next), None) | (None, Some(next)) => f(acc, next),
+            _ => acc,
+        })
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (a_len, b_len) = self.0.lens();
         // No checked_add, because even if a and b refer to the same set,
         // and T is an empty type, the storage overhead of sets limits
         // the number of elements to less than half the range of usize.
-        (0, Some(a_len + b_len))
+        // Each element present in both sets takes one from either side.
+        (a_len.abs_diff(b_len), Some(a_len + b_len))
     }
 }
 
@@ -1291,14 +1506,53 @@ impl<T> Clone for Intersection<'_, T> {
             b_range: self.b_range.clone(),
             a_set: self.a_set,
             b_set: self.b_set,
+            next_count_max: self.next_count_max,
+            max_len: self.max_len,
         }
     }
 }
+impl<T: Ord> Intersection<'_, T> {
+    /// Skips the elements below the bound, so that the next element returned
+    /// is the first one not below the bound. Repositions the underlying
+    /// iterators by searching the sets, rather than by stepping through them.
+    /// Does nothing if the iteration already went past the bound.
+    pub fn advance_to(&mut self, bound: &T) {
+        advance_range(self.a_set, &mut self.a_range, bound);
+        advance_range(self.b_set, &mut self.b_range, bound);
+    }
+}
 impl<'a, T: Ord> Iterator for Intersection<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
-        const NEXT_COUNT_MAX: usize = ITER_PERFORMANCE_TIPPING_SIZE_DIFF;
+        // Bounds within which next_count_max adapts.
+        const NEXT_COUNT_MAX_MIN: usize = 1;
+        const NEXT_COUNT_MAX_MAX: usize =
+            ITER_PERFORMANCE_TIPPING_SIZE_DIFF * ITER_PERFORMANCE_TIPPING_SIZE_DIFF;
+
+        // Returns the first element in range that is not less than bound.
+        // Before re-seeking, step as many times again as we already did:
+        // if that reaches bound, re-seeking would have been unproductive,
+        // so we should step longer next time. Otherwise, re-seeking skips
+        // a large number of elements, so we should re-seek sooner next time.
+        fn seek<'b, S: Ord>(
+            range: &mut Range<'b, S>,
+            set: &'b BTreeSet<S>,
+            bound: &'b S,
+            next_count_max: &mut usize,
+        ) -> Option<&'b S> {
+            for _ in 0..*next_count_max {
+                let next = range.next()?;
+                if next >= bound {
+                    *next_count_max = min(*next_count_max * 2, NEXT_COUNT_MAX_MAX);
+                    return Some(next);
+                }
+            }
+            *next_count_max = max(*next_count_max / 2, NEXT_COUNT_MAX_MIN);
+            *range = set.range(bound..);
+            range.next()
+        }
+
         let mut next_count: usize = 0;
         let mut a_next = self.a_range.next()?;
         let mut b_next = self.b_range.next()?;
@@ -1306,29 +1560,42 @@ impl<'a, T: Ord> Iterator for Intersection<'a, T> {
             match a_next.cmp(b_next) {
                 Less => {
                     next_count += 1;
-                    if next_count > NEXT_COUNT_MAX {
+                    a_next = if next_count > self.next_count_max {
                         next_count = 0;
-                        self.a_range = self.a_set.range(b_next..);
+                        seek(
+                            &mut self.a_range,
+                            self.a_set,
+                            b_next,
+                            &mut self.next_count_max,
+                        )?
+                    } else {
+                        self.a_range.next()?
                     }
-                    a_next = self.a_range.next()?
                 }
                 Greater => {
                     next_count += 1;
-                    if next_count > NEXT_COUNT_MAX {
+                    b_next = if next_count > self.next_count_max {
                         next_count = 0;
-                        self.b_range = self.b_set.range(a_next..);
+                        seek(
+                            &mut self.b_range,
+                            self.b_set,
+                            a_next,
+                            &mut self.next_count_max,
+                        )?
+                    } else {
+                        self.b_range.next()?
                     }
-                    b_next = self.b_range.next()?
                 }
-                Equal => return Some(a_next),
+                Equal => {
+                    self.max_len -= 1;
+                    return Some(a_next);
+                }
             }
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let a_len = self.a_set.len();
-        let b_len = self.b_set.len();
-        (0, Some(min(a_len, b_len)))
+        (0, Some(self.max_len))
     }
 }
 
@@ -1336,21 +1603,53 @@ impl<T: Ord> FusedIterator for Intersection<'_, T> {}
 
 impl<T> Clone for Union<'_, T> {
     fn clone(&self) -> Self {
-        Union(self.0.clone())
+        Union {
+            merge: self.merge.clone(),
+            a_set: self.a_set,
+            b_set: self.b_set,
+            min_len: self.min_len,
+            max_len: self.max_len,
+        }
+    }
+}
+impl<T: Ord> Union<'_, T> {
+    /// Skips the elements below the bound, so that the next element returned
+    /// is the first one not below the bound. Repositions the underlying
+    /// iterators by searching the sets, rather than by stepping through them.
+    /// Does nothing if the iteration already went past the bound.
+    pub fn advance_to(&mut self, bound: &T) {
+        self.merge.advance_to(self.a_set, self.b_set, bound);
+        // We don't know how many elements we skipped.
+        self.min_len = 0;
     }
 }
 impl<'a, T: Ord> Iterator for Union<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
-        let (a_next, b_next) = self.0.nexts();
-        a_next.or(b_next)
+        let (a_next, b_next) = self.merge.nexts();
+        let next = a_next.or(b_next);
+        if next.is_some() {
+            // Each element returned takes at least one element from either
+            // set, and at most one from each.
+            self.min_len = self.min_len.saturating_sub(1);
+            self.max_len -= 1;
+        }
+        next
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.merge.fold(init, |acc, nexts| match nexts {
+            (Some(next), _) | (None, Some(next)) => f(acc, next),
+            (None, None) => acc,
+        })
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (a_len, b_len) = self.0.lens();
-        // No checked_add - see SymmetricDifference::size_hint.
-        (max(a_len, b_len), Some(a_len + b_len))
+        (self.min_len, Some(self.max_len))
     }
 }
 
@@ -1381,3 +1680,21 @@ pub fn symmdiff<'a, T: Ord>(
 pub fn union<'a, T: Ord>(selve: &'a BTreeSet<T>, other: &'a BTreeSet<T>) -> Union<'a, T> {
     (selve as &dyn JustToIndentAsMuch<T>).union(other)
 }
+
+// Owned results, as returned by the operators on `&BTreeSet`.
+
+pub fn bitand<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(intersection(selve, other).cloned())
+}
+
+pub fn bitor<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(union(selve, other).cloned())
+}
+
+pub fn bitxor<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(symmdiff(selve, other).cloned())
+}
+
+pub fn sub<T: Ord + Clone>(selve: &BTreeSet<T>, other: &BTreeSet<T>) -> BTreeSet<T> {
+    from_sorted(difference(selve, other).cloned())
+}
@@ -0,0 +1,163 @@
+// Sorted, deduplicated views on elements, for set operations on more than BTreeSet.
+use core::iter::{FusedIterator, Map};
+use core::ops::Bound::{self, Excluded, Included};
+use std::collections::{btree_map, btree_set, BTreeMap, BTreeSet};
+
+/// A view on elements in ascending order, without duplicates,
+/// telling as much as it can without iterating.
+pub trait SortedView<'a, T: 'a> {
+    type Iter: DoubleEndedIterator<Item = &'a T> + FusedIterator + Clone;
+
+    /// Iterates over the elements, in ascending order.
+    fn iter(&self) -> Self::Iter;
+
+    /// Returns the number of elements, if known without counting.
+    fn len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns whether there are no elements.
+    fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Returns whether `contains` and `seek` answer without iterating.
+    fn can_search(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the view contains the value,
+    /// if it can tell without iterating.
+    fn contains(&self, _value: &T) -> Option<bool> {
+        None
+    }
+
+    /// Advances an iterator obtained from this view to the first remaining
+    /// element not below the value, if it can do so without iterating.
+    /// Returns whether it did.
+    fn seek(&self, _iter: &mut Self::Iter, _value: &T) -> bool {
+        false
+    }
+}
+
+/// Advances a range iterator to the first remaining element not below
+/// the value, by getting a new range from the tree.
+/// Never rewinds, and keeps the upper end, in case the iterator
+/// was used from the back.
+fn seek_range<'a: 'b, 'b, T, I, F>(iter: &mut I, value: &'b T, range: F)
+where
+    T: Ord + 'a,
+    I: DoubleEndedIterator<Item = &'a T> + Clone,
+    F: FnOnce((Bound<&'b T>, Bound<&'b T>)) -> I,
+{
+    if let (Some(first), Some(last)) = (iter.clone().next(), iter.clone().next_back()) {
+        if first < value {
+            *iter = if value <= last {
+                range((Included(value), Included(last)))
+            } else {
+                range((Included(last), Excluded(last)))
+            };
+        }
+    }
+}
+
+impl<'a, T: Ord + 'a> SortedView<'a, T> for &'a BTreeSet<T> {
+    type Iter = btree_set::Range<'a, T>;
+
+    fn iter(&self) -> Self::Iter {
+        self.range(..)
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(BTreeSet::len(self))
+    }
+
+    fn can_search(&self) -> bool {
+        true
+    }
+
+    fn contains(&self, value: &T) -> Option<bool> {
+        Some(BTreeSet::contains(self, value))
+    }
+
+    fn seek(&self, iter: &mut Self::Iter, value: &T) -> bool {
+        seek_range(iter, value, |bounds| self.range(bounds));
+        true
+    }
+}
+
+impl<'a, T: Ord + 'a> SortedView<'a, T> for btree_set::Range<'a, T> {
+    type Iter = btree_set::Range<'a, T>;
+
+    fn iter(&self) -> Self::Iter {
+        self.clone()
+    }
+}
+
+impl<'a, T: Ord + 'a> SortedView<'a, T> for &'a [T] {
+    type Iter = core::slice::Iter<'a, T>;
+
+    fn iter(&self) -> Self::Iter {
+        <[T]>::iter(self)
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(<[T]>::len(self))
+    }
+
+    fn can_search(&self) -> bool {
+        true
+    }
+
+    fn contains(&self, value: &T) -> Option<bool> {
+        Some(self.binary_search(value).is_ok())
+    }
+
+    fn seek(&self, iter: &mut Self::Iter, value: &T) -> bool {
+        let rest = iter.as_slice();
+        *iter = rest[rest.partition_point(|elt| elt < value)..].iter();
+        true
+    }
+}
+
+type KeysRange<'a, K, V> = Map<btree_map::Range<'a, K, V>, fn((&'a K, &'a V)) -> &'a K>;
+
+fn key<'a, K, V>((key, _): (&'a K, &'a V)) -> &'a K {
+    key
+}
+
+impl<'a, K: Ord + 'a, V: 'a> SortedView<'a, K> for &'a BTreeMap<K, V> {
+    type Iter = KeysRange<'a, K, V>;
+
+    fn iter(&self) -> Self::Iter {
+        self.range(..).map(key as fn(_) -> _)
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(BTreeMap::len(self))
+    }
+
+    fn can_search(&self) -> bool {
+        true
+    }
+
+    fn contains(&self, value: &K) -> Option<bool> {
+        Some(self.contains_key(value))
+    }
+
+    fn seek(&self, iter: &mut Self::Iter, value: &K) -> bool {
+        seek_range(iter, value, |bounds| {
+            self.range(bounds).map(key as fn(_) -> _)
+        });
+        true
+    }
+}
+
+/// Builds a set from elements in ascending order, without duplicates.
+///
+/// The standard library doesn't expose its bulk build from sorted elements,
+/// but collect() gets there: its sort spots that the elements are already
+/// in order in a single pass, and then bulk builds the set.
+pub fn from_sorted<T: Ord, I: IntoIterator<Item = T>>(iter: I) -> BTreeSet<T> {
+    iter.into_iter().collect()
+}
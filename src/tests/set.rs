@@ -1,5 +1,6 @@
 extern crate proptest;
 use self::proptest::prelude::*;
+use std::cmp::Ordering;
 use std::collections::BTreeSet;
 
 fn assert_difference<'a, I: Iterator<Item = &'a u8>>(
@@ -9,7 +10,7 @@ fn assert_difference<'a, I: Iterator<Item = &'a u8>>(
 ) -> Result<(), TestCaseError> {
     let mut count: usize = 0;
     let mut previous: i32 = -1;
-    while let Some(&elt) = it.next() {
+    for &elt in it.by_ref() {
         prop_assert!(s1.contains(&elt));
         prop_assert!(!s2.contains(&elt));
         prop_assert!(i32::from(elt) > previous);
@@ -30,7 +31,7 @@ fn assert_intersection<'a, I: Iterator<Item = &'a u8>>(
 ) -> Result<(), TestCaseError> {
     let mut count: usize = 0;
     let mut previous: i32 = -1;
-    while let Some(&elt) = it.next() {
+    for &elt in it.by_ref() {
         prop_assert!(s1.contains(&elt));
         prop_assert!(s2.contains(&elt));
         prop_assert!(i32::from(elt) > previous);
@@ -51,7 +52,7 @@ fn assert_symmdiff<'a, I: Iterator<Item = &'a u8>>(
 ) -> Result<(), TestCaseError> {
     let mut count: usize = 0;
     let mut previous: i32 = -1;
-    while let Some(&elt) = it.next() {
+    for &elt in it.by_ref() {
         prop_assert_eq!(s1.contains(&elt), !s2.contains(&elt));
         prop_assert!(i32::from(elt) > previous);
         count += 1;
@@ -74,7 +75,7 @@ fn assert_union<'a, I: Iterator<Item = &'a u8>>(
 ) -> Result<(), TestCaseError> {
     let mut count: usize = 0;
     let mut previous: i32 = -1;
-    while let Some(&elt) = it.next() {
+    for &elt in it.by_ref() {
         prop_assert!(s1.contains(&elt) || s2.contains(&elt));
         prop_assert!(i32::from(elt) > previous);
         count += 1;
@@ -90,16 +91,64 @@ fn assert_union<'a, I: Iterator<Item = &'a u8>>(
     Ok(())
 }
 
+// Checks that folding, after stepping some elements, resumes where stepping left off.
+fn assert_fold<'a, I: Iterator<Item = &'a u8>>(
+    mut it: I,
+    skip: usize,
+    expected: Vec<&'a u8>,
+) -> Result<(), TestCaseError> {
+    let elts: Vec<&u8> = it.by_ref().take(skip).collect();
+    let elts = it.fold(elts, |mut elts, elt| {
+        elts.push(elt);
+        elts
+    });
+    prop_assert_eq!(elts, expected);
+    Ok(())
+}
+
+fn assert_size_hints<I: Iterator>(mut it: I, len: usize) -> Result<(), TestCaseError> {
+    for left in (0..=len).rev() {
+        let (min_len, max_len) = it.size_hint();
+        prop_assert!(
+            min_len <= left,
+            "lower bound {} with {} left",
+            min_len,
+            left
+        );
+        prop_assert!(
+            max_len.is_none_or(|max_len| left <= max_len),
+            "upper bound {:?} with {} left",
+            max_len,
+            left
+        );
+        prop_assert_eq!(it.next().is_some(), left > 0);
+    }
+    Ok(())
+}
+
+fn assert_ends<'a, I: Iterator<Item = &'a u8> + Clone>(
+    mut it: I,
+    skip: usize,
+    expected: Vec<&'a u8>,
+) -> Result<(), TestCaseError> {
+    it.by_ref().take(skip).for_each(drop);
+    let expected = &expected[skip.min(expected.len())..];
+    prop_assert_eq!(it.clone().last(), expected.last().copied());
+    prop_assert_eq!(it.clone().max(), expected.last().copied());
+    prop_assert_eq!(it.min(), expected.first().copied());
+    Ok(())
+}
+
 prop_compose! {
     fn aligned_ranges()
                      (mut s1: BTreeSet<u8>,
                       mut s2: BTreeSet<u8>)
                      -> (BTreeSet<u8>, BTreeSet<u8>)
     {
-        s1.insert(u8::min_value());
-        s2.insert(u8::min_value());
-        s1.insert(u8::max_value());
-        s2.insert(u8::max_value());
+        s1.insert(u8::MIN);
+        s2.insert(u8::MIN);
+        s1.insert(u8::MAX);
+        s2.insert(u8::MAX);
         (s1, s2)
     }
 }
@@ -110,8 +159,8 @@ prop_compose! {
                            mut s2: BTreeSet<u8>)
                           -> (BTreeSet<u8>, BTreeSet<u8>)
     {
-        s1.insert(u8::min_value());
-        s2.insert(u8::min_value());
+        s1.insert(u8::MIN);
+        s2.insert(u8::MIN);
         (s1, s2)
     }
 }
@@ -122,8 +171,8 @@ prop_compose! {
                             mut s2: BTreeSet<u8>)
                            -> (BTreeSet<u8>, BTreeSet<u8>)
     {
-        s1.insert(u8::max_value());
-        s2.insert(u8::max_value());
+        s1.insert(u8::MAX);
+        s2.insert(u8::MAX);
         (s1, s2)
     }
 }
@@ -134,10 +183,10 @@ prop_compose! {
                        right_then_left: bool)
                       -> (BTreeSet<u8>, BTreeSet<u8>)
     {
-        let split = (u8::max_value() - u8::min_value()) / 2;
+        let split = u8::MAX / 2;
         let mut s2 = s1.split_off(&split);
-        s1.insert(u8::min_value());
-        s2.insert(u8::max_value());
+        s1.insert(u8::MIN);
+        s2.insert(u8::MAX);
         if right_then_left { (s2, s1) } else { (s1, s2) }
     }
 }
@@ -148,7 +197,7 @@ prop_compose! {
                        right_then_left: bool)
                       -> (BTreeSet<u8>, BTreeSet<u8>)
     {
-        let split = (u8::max_value() - u8::min_value()) / 2;
+        let split = u8::MAX / 2;
         let mut s2 = s1.split_off(&split);
         s1.insert(split);
         s2.insert(split);
@@ -156,160 +205,412 @@ prop_compose! {
     }
 }
 
+prop_compose! {
+    fn overlapping_ranges()
+                         (s1: BTreeSet<u8>,
+                          s2: BTreeSet<u8>,
+                          right_then_left: bool)
+                         -> (BTreeSet<u8>, BTreeSet<u8>)
+    {
+        let s1: BTreeSet<u8> = s1.range(..=u8::MAX / 4 * 3).cloned().collect();
+        let s2: BTreeSet<u8> = s2.range(u8::MAX / 4..).cloned().collect();
+        if right_then_left { (s2, s1) } else { (s1, s2) }
+    }
+}
+
+prop_compose! {
+    fn lopsided_ranges()
+                      (s1: BTreeSet<u8>,
+                       s2: BTreeSet<u8>,
+                       right_then_left: bool)
+                      -> (BTreeSet<u8>, BTreeSet<u8>)
+    {
+        // Each set is dense on one side of the split and sparse on the other,
+        // so the sizes remaining to be visited change drastically halfway.
+        let split = u8::MAX / 2;
+        let sparse = |elt: &&u8| elt.is_multiple_of(16);
+        let mut s1: BTreeSet<u8> = s1.range(split..).filter(sparse).cloned().collect();
+        let mut s2: BTreeSet<u8> = s2.range(..split).filter(sparse).cloned().collect();
+        s1.extend(0..split);
+        s2.extend(split..=u8::MAX);
+        if right_then_left { (s2, s1) } else { (s1, s2) }
+    }
+}
+
+// The relative size from which set_new and set_peeking splice a small set
+// into a large one, rather than merging them.
+const SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF: usize = 64;
+
+prop_compose! {
+    fn tiny_vs_large()
+                    (s1: BTreeSet<u8>,
+                     s2: BTreeSet<u8>,
+                     right_then_left: bool)
+                    -> (BTreeSet<u8>, BTreeSet<u8>)
+    {
+        // Few enough elements against at least 255 to splice.
+        let tiny_len = usize::from(u8::MAX) / SPLICE_PERFORMANCE_TIPPING_SIZE_DIFF;
+        let step = (usize::from(u8::MAX) + tiny_len) / tiny_len;
+        let s1: BTreeSet<u8> = s1.iter().filter(|&&elt| usize::from(elt).is_multiple_of(step)).cloned().collect();
+        let mut s2 = s2;
+        s2.extend(1..=u8::MAX);
+        if right_then_left { (s2, s1) } else { (s1, s2) }
+    }
+}
+
+prop_compose! {
+    fn nearly_equal()
+                   (s1: BTreeSet<u8>,
+                    toggles: Vec<u8>)
+                   -> (BTreeSet<u8>, BTreeSet<u8>)
+    {
+        // The same set, with only a few elements added or removed.
+        let mut s2 = s1.clone();
+        for elt in toggles.into_iter().take(3) {
+            if !s2.remove(&elt) {
+                s2.insert(elt);
+            }
+        }
+        (s1, s2)
+    }
+}
+
+// Generates a proptest for each kind of pair of sets above, running the
+// assertion on the pair and on any further arguments, generated by the
+// strategies given along with their names.
+macro_rules! fixture_tests {
+    ($assert: path $(, $arg: ident in $strategy: expr)*) => {
+        super::proptest! {
+            #[test]
+            fn arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8> $(, $arg in $strategy)*) {
+                $assert(&s1, &s2 $(, $arg)*)?
+            }
+
+            #[test]
+            fn aligned_both((s1, s2) in super::aligned_ranges() $(, $arg in $strategy)*) {
+                $assert(&s1, &s2 $(, $arg)*)?
+            }
+
+            #[test]
+            fn disjoint((s1, s2) in super::disjoint_ranges() $(, $arg in $strategy)*) {
+                $assert(&s1, &s2 $(, $arg)*)?
+            }
+
+            #[test]
+            fn touching((s1, s2) in super::touching_ranges() $(, $arg in $strategy)*) {
+                $assert(&s1, &s2 $(, $arg)*)?
+            }
+
+            #[test]
+            fn overlapping((s1, s2) in super::overlapping_ranges() $(, $arg in $strategy)*) {
+                $assert(&s1, &s2 $(, $arg)*)?
+            }
+
+            #[test]
+            fn lopsided((s1, s2) in super::lopsided_ranges() $(, $arg in $strategy)*) {
+                $assert(&s1, &s2 $(, $arg)*)?
+            }
+
+            #[test]
+            fn tiny_vs_large((s1, s2) in super::tiny_vs_large() $(, $arg in $strategy)*) {
+                $assert(&s1, &s2 $(, $arg)*)?
+            }
+
+            #[test]
+            fn nearly_equal((s1, s2) in super::nearly_equal() $(, $arg in $strategy)*) {
+                $assert(&s1, &s2 $(, $arg)*)?
+            }
+        }
+    };
+}
+
 macro_rules! set_tests {
     ($test_mod_name: ident, $mod_name: ident) => {
+        set_tests!($test_mod_name, $mod_name, std::convert::identity);
+    };
+    ($test_mod_name: ident, $mod_name: ident, $prepare: path) => {
         mod $test_mod_name {
             use rust_bench_btreeset::$mod_name;
             use std::collections::BTreeSet;
+
+            fn assert_folds(
+                s1: &BTreeSet<u8>,
+                s2: &BTreeSet<u8>,
+                skip: usize,
+            ) -> Result<(), super::TestCaseError> {
+                let (p1, p2) = ($prepare(s1), $prepare(s2));
+                super::assert_fold($mod_name::difference(&*p1, &*p2), skip, s1.difference(s2).collect())?;
+                super::assert_fold($mod_name::intersection(&*p1, &*p2), skip, s1.intersection(s2).collect())?;
+                super::assert_fold($mod_name::symmdiff(&*p1, &*p2), skip, s1.symmetric_difference(s2).collect())?;
+                super::assert_fold($mod_name::union(&*p1, &*p2), skip, s1.union(s2).collect())
+            }
+            fn assert_size_hints(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+                let (p1, p2) = ($prepare(s1), $prepare(s2));
+                super::assert_size_hints($mod_name::difference(&*p1, &*p2), s1.difference(s2).count())?;
+                super::assert_size_hints($mod_name::intersection(&*p1, &*p2), s1.intersection(s2).count())?;
+                super::assert_size_hints($mod_name::symmdiff(&*p1, &*p2), s1.symmetric_difference(s2).count())?;
+                super::assert_size_hints($mod_name::union(&*p1, &*p2), s1.union(s2).count())
+            }
             super::proptest! {
                 #[test]
                 fn difference_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
-                    super::assert_difference($mod_name::difference(&s1, &s2), &s1, &s2)?
+                    super::assert_difference($mod_name::difference(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn difference_aligned_left((s1, s2) in super::left_aligned_ranges()) {
-                    super::assert_difference($mod_name::difference(&s1, &s2), &s1, &s2)?
+                    super::assert_difference($mod_name::difference(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn difference_aligned_right((s1, s2) in super::right_aligned_ranges()) {
-                    super::assert_difference($mod_name::difference(&s1, &s2), &s1, &s2)?
+                    super::assert_difference($mod_name::difference(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn difference_aligned_both((s1, s2) in super::aligned_ranges()) {
-                    super::assert_difference($mod_name::difference(&s1, &s2), &s1, &s2)?
+                    super::assert_difference($mod_name::difference(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn difference_disjoint((s1, s2) in super::disjoint_ranges()) {
-                    super::assert_difference($mod_name::difference(&s1, &s2), &s1, &s2)?
+                    super::assert_difference($mod_name::difference(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
+                }
+
+                #[test]
+                fn difference_overlapping((s1, s2) in super::overlapping_ranges()) {
+                    super::assert_difference($mod_name::difference(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn difference_touching((s1, s2) in super::touching_ranges()) {
-                    super::assert_difference($mod_name::difference(&s1, &s2), &s1, &s2)?
+                    super::assert_difference($mod_name::difference(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
+                }
+
+                #[test]
+                fn difference_lopsided((s1, s2) in super::lopsided_ranges()) {
+                    super::assert_difference($mod_name::difference(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
+                }
+
+                #[test]
+                fn difference_tiny_vs_large((s1, s2) in super::tiny_vs_large()) {
+                    super::assert_difference($mod_name::difference(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn is_subset_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
-                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&s1, &s2));
+                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&*$prepare(&s1), &*$prepare(&s2)));
                 }
 
                 #[test]
                 fn is_subset_aligned_left((s1, s2) in super::left_aligned_ranges()) {
-                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&s1, &s2));
+                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&*$prepare(&s1), &*$prepare(&s2)));
                 }
 
                 #[test]
                 fn is_subset_aligned_right((s1, s2) in super::right_aligned_ranges()) {
-                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&s1, &s2));
+                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&*$prepare(&s1), &*$prepare(&s2)));
                 }
 
                 #[test]
                 fn is_subset_aligned_both((s1, s2) in super::aligned_ranges()) {
-                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&s1, &s2));
+                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&*$prepare(&s1), &*$prepare(&s2)));
                 }
 
                 #[test]
                 fn is_subset_disjoint((s1, s2) in super::disjoint_ranges()) {
-                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&s1, &s2));
+                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&*$prepare(&s1), &*$prepare(&s2)));
                 }
 
                 #[test]
                 fn is_subset_touching((s2, s1) in super::touching_ranges()) {
-                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&s1, &s2));
+                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&*$prepare(&s1), &*$prepare(&s2)));
+                }
+
+                #[test]
+                fn is_subset_lopsided((s1, s2) in super::lopsided_ranges()) {
+                    let s2 = &s1 | &s2;
+                    super::prop_assert_eq!(s1.is_subset(&s2), $mod_name::is_subset(&*$prepare(&s1), &*$prepare(&s2)));
                 }
 
                 #[test]
                 fn intersection_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
-                    super::assert_intersection($mod_name::intersection(&s1, &s2), &s1, &s2)?
+                    super::assert_intersection($mod_name::intersection(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn intersection_aligned_left((s1, s2) in super::left_aligned_ranges()) {
-                    super::assert_intersection($mod_name::intersection(&s1, &s2), &s1, &s2)?
+                    super::assert_intersection($mod_name::intersection(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn intersection_aligned_right((s1, s2) in super::right_aligned_ranges()) {
-                    super::assert_intersection($mod_name::intersection(&s1, &s2), &s1, &s2)?
+                    super::assert_intersection($mod_name::intersection(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn intersection_aligned_both((s1, s2) in super::aligned_ranges()) {
-                    super::assert_intersection($mod_name::intersection(&s1, &s2), &s1, &s2)?
+                    super::assert_intersection($mod_name::intersection(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn intersection_disjoint1((s1, s2) in super::disjoint_ranges()) {
-                    super::assert_intersection($mod_name::intersection(&s1, &s2), &s1, &s2)?
+                    super::assert_intersection($mod_name::intersection(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
+                }
+
+                #[test]
+                fn intersection_overlapping((s1, s2) in super::overlapping_ranges()) {
+                    super::assert_intersection($mod_name::intersection(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn intersection_touching((s2, s1) in super::touching_ranges()) {
-                    super::assert_intersection($mod_name::intersection(&s1, &s2), &s1, &s2)?
+                    super::assert_intersection($mod_name::intersection(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn symmdiff_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
-                    super::assert_symmdiff($mod_name::symmdiff(&s1, &s2), &s1, &s2)?
+                    super::assert_symmdiff($mod_name::symmdiff(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn symmdiff_aligned_left((s1, s2) in super::left_aligned_ranges()) {
-                    super::assert_symmdiff($mod_name::symmdiff(&s1, &s2), &s1, &s2)?
+                    super::assert_symmdiff($mod_name::symmdiff(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn symmdiff_aligned_right((s1, s2) in super::right_aligned_ranges()) {
-                    super::assert_symmdiff($mod_name::symmdiff(&s1, &s2), &s1, &s2)?
+                    super::assert_symmdiff($mod_name::symmdiff(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn symmdiff_aligned_both((s1, s2) in super::aligned_ranges()) {
-                    super::assert_symmdiff($mod_name::symmdiff(&s1, &s2), &s1, &s2)?
+                    super::assert_symmdiff($mod_name::symmdiff(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn symmdiff_disjoint1((s1, s2) in super::disjoint_ranges()) {
-                    super::assert_symmdiff($mod_name::symmdiff(&s1, &s2), &s1, &s2)?
+                    super::assert_symmdiff($mod_name::symmdiff(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
+                }
+
+                #[test]
+                fn symmdiff_overlapping((s1, s2) in super::overlapping_ranges()) {
+                    super::assert_symmdiff($mod_name::symmdiff(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn symmdiff_touching((s2, s1) in super::touching_ranges()) {
-                    super::assert_symmdiff($mod_name::symmdiff(&s1, &s2), &s1, &s2)?
+                    super::assert_symmdiff($mod_name::symmdiff(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
+                }
+
+                #[test]
+                fn symmdiff_tiny_vs_large((s1, s2) in super::tiny_vs_large()) {
+                    super::assert_symmdiff($mod_name::symmdiff(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn union_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
-                    super::assert_union($mod_name::union(&s1, &s2), &s1, &s2)?
+                    super::assert_union($mod_name::union(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn union_aligned_left((s1, s2) in super::left_aligned_ranges()) {
-                    super::assert_union($mod_name::union(&s1, &s2), &s1, &s2)?
+                    super::assert_union($mod_name::union(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn union_aligned_right((s1, s2) in super::right_aligned_ranges()) {
-                    super::assert_union($mod_name::union(&s1, &s2), &s1, &s2)?
+                    super::assert_union($mod_name::union(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn union_aligned_both((s1, s2) in super::aligned_ranges()) {
-                    super::assert_union($mod_name::union(&s1, &s2), &s1, &s2)?
+                    super::assert_union($mod_name::union(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn union_disjoint1((s1, s2) in super::disjoint_ranges()) {
-                    super::assert_union($mod_name::union(&s1, &s2), &s1, &s2)?
+                    super::assert_union($mod_name::union(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
+                }
+
+                #[test]
+                fn union_overlapping((s1, s2) in super::overlapping_ranges()) {
+                    super::assert_union($mod_name::union(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
                 }
 
                 #[test]
                 fn union_touching((s2, s1) in super::touching_ranges()) {
-                    super::assert_union($mod_name::union(&s1, &s2), &s1, &s2)?
+                    super::assert_union($mod_name::union(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
+                }
+
+                #[test]
+                fn union_tiny_vs_large((s1, s2) in super::tiny_vs_large()) {
+                    super::assert_union($mod_name::union(&*$prepare(&s1), &*$prepare(&s2)), &s1, &s2)?
+                }
+
+                #[test]
+                fn fold_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>, skip in 0..3usize) {
+                    assert_folds(&s1, &s2, skip)?
+                }
+
+                #[test]
+                fn fold_disjoint((s1, s2) in super::disjoint_ranges(), skip in 0..3usize) {
+                    assert_folds(&s1, &s2, skip)?
+                }
+
+                #[test]
+                fn fold_lopsided((s1, s2) in super::lopsided_ranges(), skip in 0..3usize) {
+                    assert_folds(&s1, &s2, skip)?;
+                    assert_folds(&s2, &s1, skip)?
+                }
+
+                #[test]
+                fn fold_tiny_vs_large((s1, s2) in super::tiny_vs_large(), skip in 0..3usize) {
+                    assert_folds(&s1, &s2, skip)?;
+                    assert_folds(&s2, &s1, skip)?
+                }
+
+                #[test]
+                fn size_hint_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
+                    assert_size_hints(&s1, &s2)?
+                }
+
+                #[test]
+                fn size_hint_disjoint((s1, s2) in super::disjoint_ranges()) {
+                    assert_size_hints(&s1, &s2)?;
+                    assert_size_hints(&s2, &s1)?
+                }
+
+                #[test]
+                fn size_hint_lopsided((s1, s2) in super::lopsided_ranges()) {
+                    assert_size_hints(&s1, &s2)?;
+                    assert_size_hints(&s2, &s1)?
+                }
+
+                #[test]
+                fn size_hint_tiny_vs_large((s1, s2) in super::tiny_vs_large()) {
+                    assert_size_hints(&s1, &s2)?;
+                    assert_size_hints(&s2, &s1)?
+                }
+
+                #[test]
+                fn bitand_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
+                    super::prop_assert_eq!($mod_name::bitand(&*$prepare(&s1), &*$prepare(&s2)), $prepare(&(&s1 & &s2)).to_owned());
+                }
+
+                #[test]
+                fn bitor_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
+                    super::prop_assert_eq!($mod_name::bitor(&*$prepare(&s1), &*$prepare(&s2)), $prepare(&(&s1 | &s2)).to_owned());
+                }
+
+                #[test]
+                fn bitxor_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
+                    super::prop_assert_eq!($mod_name::bitxor(&*$prepare(&s1), &*$prepare(&s2)), $prepare(&(&s1 ^ &s2)).to_owned());
+                }
+
+                #[test]
+                fn sub_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
+                    super::prop_assert_eq!($mod_name::sub(&*$prepare(&s1), &*$prepare(&s2)), $prepare(&(&s1 - &s2)).to_owned());
                 }
             }
         }
@@ -321,3 +622,1142 @@ set_tests! {test_new, set_new}
 set_tests! {test_peeking, set_peeking}
 set_tests! {test_switch, set_switch}
 set_tests! {test_swivel, set_swivel}
+set_tests! {test_slice, set_slice, super::to_vec}
+
+// The sorted slice holding the same elements as a set.
+// An element that remembers which set it came from,
+// and can only be moved, not cloned.
+#[derive(Debug)]
+struct Tagged {
+    key: u8,
+    from_a: bool,
+}
+
+impl PartialEq for Tagged {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Tagged {}
+
+impl PartialOrd for Tagged {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tagged {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+fn tag(s: &BTreeSet<u8>, from_a: bool) -> BTreeSet<Tagged> {
+    s.iter().map(|&key| Tagged { key, from_a }).collect()
+}
+
+fn to_vec(set: &BTreeSet<u8>) -> Vec<u8> {
+    set.iter().copied().collect()
+}
+
+mod test_slice_strategies {
+    use rust_bench_btreeset::set_slice;
+    use std::collections::BTreeSet;
+
+    fn assert_strategies(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        let (v1, v2) = (super::to_vec(s1), super::to_vec(s2));
+        super::assert_intersection(set_slice::intersection_search(&v1, &v2), s1, s2)?;
+        super::assert_intersection(set_slice::intersection_gallop(&v1, &v2), s1, s2)?;
+        super::assert_intersection(set_slice::intersection_stitch(&v1, &v2), s1, s2)
+    }
+
+    fixture_tests! {assert_strategies}
+}
+
+mod test_switch_vs_now {
+    use rust_bench_btreeset::{set_now, set_switch};
+    use std::collections::BTreeSet;
+    super::proptest! {
+        #[test]
+        fn difference_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
+            super::prop_assert_eq!(
+                set_switch::difference(&s1, &s2).collect::<Vec<_>>(),
+                set_now::difference(&s1, &s2).collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn difference_lopsided((s1, s2) in super::lopsided_ranges()) {
+            super::prop_assert_eq!(
+                set_switch::difference(&s1, &s2).collect::<Vec<_>>(),
+                set_now::difference(&s1, &s2).collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn is_subset_arbitrary(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
+            super::prop_assert_eq!(set_switch::is_subset(&s1, &s2), set_now::is_subset(&s1, &s2));
+        }
+
+        #[test]
+        fn is_subset_lopsided((s1, s2) in super::lopsided_ranges()) {
+            let s2 = &s1 | &s2;
+            super::prop_assert_eq!(set_switch::is_subset(&s1, &s2), set_now::is_subset(&s1, &s2));
+        }
+    }
+}
+
+mod test_splice {
+    use rust_bench_btreeset::{set_new, set_peeking};
+    use std::collections::BTreeSet;
+
+    fn assert_forced(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        super::assert_difference(set_new::difference_splice(s1, s2), s1, s2)?;
+        super::assert_difference(set_peeking::difference_splice(s1, s2), s1, s2)?;
+        super::assert_difference(set_peeking::difference_stitch(s1, s2), s1, s2)?;
+        super::assert_symmdiff(set_new::symmdiff_splice(s1, s2), s1, s2)?;
+        super::assert_symmdiff(set_peeking::symmdiff_splice(s1, s2), s1, s2)?;
+        super::assert_symmdiff(set_peeking::symmdiff_stitch(s1, s2), s1, s2)?;
+        super::assert_union(set_new::union_splice(s1, s2), s1, s2)?;
+        super::assert_union(set_peeking::union_splice(s1, s2), s1, s2)?;
+        super::assert_union(set_peeking::union_stitch(s1, s2), s1, s2)
+    }
+
+    fn debug<D: std::fmt::Debug>(it: D) -> String {
+        format!("{:?}", it)
+    }
+
+    fn assert_chosen(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        super::prop_assert!(debug(set_new::symmdiff(s1, s2)).contains("(Splice("));
+        super::prop_assert!(debug(set_new::union(s1, s2)).contains("(Splice("));
+        super::prop_assert!(debug(set_peeking::symmdiff(s1, s2)).contains("(Splice("));
+        super::prop_assert!(debug(set_peeking::union(s1, s2)).contains("(Splice("));
+        Ok(())
+    }
+
+    #[test]
+    fn difference_chosen_for_superset_10k_vs_100() {
+        let s1: BTreeSet<u32> = (0..10_000).collect();
+        let s2: BTreeSet<u32> = (4_950..5_050).collect();
+        assert!(debug(set_new::difference(&s1, &s2)).starts_with("Difference(Splice {"));
+        assert!(debug(set_peeking::difference(&s1, &s2)).starts_with("Difference(Splice {"));
+    }
+
+    fixture_tests! {assert_forced}
+
+    super::proptest! {
+        #[test]
+        fn chosen_for_tiny_vs_large((s1, s2) in super::tiny_vs_large()) {
+            assert_chosen(&s1, &s2)?
+        }
+    }
+}
+
+mod test_with {
+    use super::tag;
+    use rust_bench_btreeset::set_new;
+    use std::collections::BTreeSet;
+
+    fn assert_intersect_with(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+    ) -> Result<(), super::TestCaseError> {
+        let mut s = s1.clone();
+        set_new::intersect_with(&mut s, s2);
+        super::prop_assert_eq!(s, s1 & s2);
+        Ok(())
+    }
+
+    fn assert_difference_with(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+    ) -> Result<(), super::TestCaseError> {
+        let mut s = s1.clone();
+        set_new::difference_with(&mut s, s2);
+        super::prop_assert_eq!(s, s1 - s2);
+        Ok(())
+    }
+
+    // Keeps the elements of s1 over the equal elements of s2.
+    fn assert_union_with(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        let mut s = tag(s1, true);
+        set_new::union_with(&mut s, tag(s2, false));
+        for elt in &s {
+            super::prop_assert_eq!(elt.from_a, s1.contains(&elt.key));
+        }
+        super::prop_assert_eq!(
+            s.iter().map(|elt| elt.key).collect::<BTreeSet<_>>(),
+            s1 | s2
+        );
+        Ok(())
+    }
+
+    fn assert_with(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        assert_intersect_with(s1, s2)?;
+        assert_difference_with(s1, s2)?;
+        assert_union_with(s1, s2)
+    }
+
+    #[test]
+    fn union_with_keeps_self_on_ties() {
+        let small: BTreeSet<u8> = [10, 20].iter().cloned().collect();
+        let large: BTreeSet<u8> = (0..100).collect();
+        let mut s = tag(&small, true);
+        set_new::union_with(&mut s, tag(&large, false));
+        let kept: Vec<u8> = s
+            .iter()
+            .filter(|elt| elt.from_a)
+            .map(|elt| elt.key)
+            .collect();
+        assert_eq!(kept, [10, 20]);
+        let mut s = tag(&large, true);
+        set_new::union_with(&mut s, tag(&small, false));
+        assert!(s.iter().all(|elt| elt.from_a));
+    }
+
+    fixture_tests! {assert_with}
+}
+
+mod test_into {
+    use super::{tag, Tagged};
+    use rust_bench_btreeset::set_new;
+    use std::collections::BTreeSet;
+
+    // Checks the result, and that it keeps the elements of s1
+    // over the equal elements of s2.
+    fn assert_into<F>(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+        oper: F,
+        expected: BTreeSet<u8>,
+    ) -> Result<(), super::TestCaseError>
+    where
+        F: FnOnce(BTreeSet<Tagged>, BTreeSet<Tagged>) -> BTreeSet<Tagged>,
+    {
+        let result = oper(tag(s1, true), tag(s2, false));
+        for elt in &result {
+            super::prop_assert_eq!(elt.from_a, s1.contains(&elt.key));
+        }
+        super::prop_assert_eq!(
+            result
+                .into_iter()
+                .map(|elt| elt.key)
+                .collect::<BTreeSet<_>>(),
+            expected
+        );
+        Ok(())
+    }
+
+    fn assert_all_into(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        assert_into(s1, s2, set_new::into_difference, s1 - s2)?;
+        assert_into(s1, s2, set_new::into_intersection, s1 & s2)?;
+        assert_into(s1, s2, set_new::into_symmetric_difference, s1 ^ s2)?;
+        assert_into(s1, s2, set_new::into_union, s1 | s2)
+    }
+
+    fixture_tests! {assert_all_into}
+}
+
+mod test_borrowed {
+    use rust_bench_btreeset::set_peeking;
+    use std::collections::BTreeSet;
+
+    // Zero padded, so that the strings sort like the numbers.
+    fn strings(s: &BTreeSet<u8>) -> BTreeSet<String> {
+        s.iter().map(|elt| format!("{:03}", elt)).collect()
+    }
+
+    fn assert_borrowed(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        let a = strings(s1);
+        let b = strings(s2);
+        let b_strs: BTreeSet<&str> = b.iter().map(String::as_str).collect();
+        let b_boxes: BTreeSet<Box<str>> = b.iter().map(|elt| elt.as_str().into()).collect();
+        let a_or_b = &a | &b;
+        let a_or_b_strs: BTreeSet<&str> = a_or_b.iter().map(String::as_str).collect();
+
+        let difference: Vec<&String> = a.difference(&b).collect();
+        super::prop_assert_eq!(
+            &set_peeking::difference_borrowed::<str, _, _>(&a, &b_strs).collect::<Vec<_>>(),
+            &difference
+        );
+        super::prop_assert_eq!(
+            &set_peeking::difference_borrowed::<str, _, _>(&a, &b_boxes).collect::<Vec<_>>(),
+            &difference
+        );
+
+        let intersection: Vec<&String> = a.intersection(&b).collect();
+        super::prop_assert_eq!(
+            &set_peeking::intersection_borrowed::<str, _, _>(&a, &b_strs).collect::<Vec<_>>(),
+            &intersection
+        );
+        super::prop_assert_eq!(
+            &set_peeking::intersection_borrowed::<str, _, _>(&a, &b_boxes).collect::<Vec<_>>(),
+            &intersection
+        );
+        super::prop_assert_eq!(
+            set_peeking::intersection_borrowed::<str, _, _>(&b_strs, &a)
+                .copied()
+                .collect::<Vec<_>>(),
+            intersection
+                .iter()
+                .map(|elt| elt.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        super::prop_assert_eq!(
+            set_peeking::is_subset_borrowed::<str, _, _>(&a, &b_strs),
+            a.is_subset(&b)
+        );
+        super::prop_assert!(set_peeking::is_subset_borrowed::<str, _, _>(
+            &a,
+            &a_or_b_strs
+        ));
+        Ok(())
+    }
+
+    fixture_tests! {assert_borrowed}
+}
+
+mod test_by_key {
+    use rust_bench_btreeset::set_peeking;
+    use std::collections::BTreeSet;
+
+    type Keyed = BTreeSet<(u8, u8)>;
+
+    fn key(elt: &(u8, u8)) -> u8 {
+        elt.0
+    }
+
+    // Few distinct keys, so that many elements share a key.
+    fn keyed(s: &BTreeSet<u8>) -> Keyed {
+        s.iter().map(|&elt| (elt % 16, elt / 16)).collect()
+    }
+
+    fn assert_by_key(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        let (s1, s2) = (&keyed(s1), &keyed(s2));
+        let expected: Vec<_> = s1
+            .iter()
+            .flat_map(|a| {
+                s2.iter()
+                    .filter(move |b| key(a) == key(b))
+                    .map(move |b| (a, b))
+            })
+            .collect();
+        let it = set_peeking::intersection_by_key(s1, s2, key, key);
+        super::assert_size_hints(it.clone(), expected.len())?;
+        super::prop_assert_eq!(it.collect::<Vec<_>>(), expected);
+
+        let expected: Vec<_> = s1
+            .iter()
+            .filter(|a| !s2.iter().any(|b| key(a) == key(b)))
+            .collect();
+        let it = set_peeking::difference_by_key(s1, s2, key, key);
+        super::prop_assert_eq!(it.collect::<Vec<_>>(), expected);
+        Ok(())
+    }
+
+    fixture_tests! {assert_by_key}
+
+    super::proptest! {
+        #[test]
+        fn different_types(s1: BTreeSet<u8>, s2: BTreeSet<u8>) {
+            // Match on the key, whatever else the elements hold.
+            let (s1, s2) = (keyed(&s1), keyed(&s2));
+            // Zero padded, so that the strings sort like the numbers.
+            let names: BTreeSet<(u8, String)> =
+                s2.iter().map(|&(k, v)| (k, format!("{:02}", v))).collect();
+            let name_key = |elt: &(u8, String)| elt.0;
+            super::prop_assert_eq!(
+                set_peeking::intersection_by_key(&s1, &names, key, name_key)
+                    .map(|(a, b)| (*a, b.clone()))
+                    .collect::<Vec<_>>(),
+                set_peeking::intersection_by_key(&s1, &s2, key, key)
+                    .map(|(&a, &(k, v))| (a, (k, format!("{:02}", v))))
+                    .collect::<Vec<_>>()
+            );
+            super::prop_assert_eq!(
+                set_peeking::difference_by_key(&s1, &names, key, name_key).collect::<Vec<_>>(),
+                set_peeking::difference_by_key(&s1, &s2, key, key).collect::<Vec<_>>()
+            );
+        }
+    }
+}
+
+mod test_pairs {
+    use rust_bench_btreeset::set_peeking;
+    use std::cmp::Ordering;
+    use std::collections::BTreeSet;
+
+    // A record ordered by its id only, remembering which set it came from.
+    #[derive(Debug)]
+    struct Record {
+        id: u8,
+        from_a: bool,
+    }
+
+    impl PartialEq for Record {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for Record {}
+
+    impl PartialOrd for Record {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Record {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.id.cmp(&other.id)
+        }
+    }
+
+    fn records(s: &BTreeSet<u8>, from_a: bool) -> BTreeSet<Record> {
+        s.iter().map(|&id| Record { id, from_a }).collect()
+    }
+
+    fn assert_pairs(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        let a = records(s1, true);
+        let b = records(s2, false);
+        let mut ids = Vec::new();
+        for (a_next, b_next) in set_peeking::intersection_pairs(&a, &b) {
+            super::prop_assert_eq!(a_next.id, b_next.id);
+            super::prop_assert!(a_next.from_a);
+            super::prop_assert!(!b_next.from_a);
+            ids.push(a_next.id);
+        }
+        super::prop_assert_eq!(ids, s1.intersection(s2).cloned().collect::<Vec<_>>());
+        Ok(())
+    }
+
+    fixture_tests! {assert_pairs}
+}
+
+mod test_join {
+    use rust_bench_btreeset::map_join;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn assert_joins(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        let a: BTreeMap<u8, u16> = s1.iter().map(|&k| (k, u16::from(k) * 3)).collect();
+        let b: BTreeMap<u8, String> = s2.iter().map(|&k| (k, k.to_string())).collect();
+
+        let inner: Vec<_> = a
+            .iter()
+            .filter_map(|(k, a_value)| b.get(k).map(|b_value| (k, a_value, b_value)))
+            .collect();
+        super::prop_assert_eq!(map_join::inner_join(&a, &b).collect::<Vec<_>>(), inner);
+
+        let semi: Vec<_> = a.iter().filter(|(k, _)| b.contains_key(k)).collect();
+        super::prop_assert_eq!(map_join::semi_join(&a, &b).collect::<Vec<_>>(), semi);
+
+        let anti: Vec<_> = a.iter().filter(|(k, _)| !b.contains_key(k)).collect();
+        let mut it = map_join::anti_join(&a, &b);
+        super::prop_assert_eq!(it.by_ref().collect::<Vec<_>>(), anti);
+        super::prop_assert!(it.next().is_none());
+        Ok(())
+    }
+
+    fixture_tests! {assert_joins}
+}
+
+mod test_classify {
+    use rust_bench_btreeset::set_peeking::{self, Merged};
+    use std::collections::BTreeSet;
+
+    fn assert_classify(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        let mut lefts = Vec::new();
+        let mut boths = Vec::new();
+        let mut rights = Vec::new();
+        let mut previous: i32 = -1;
+        for merged in set_peeking::classify(s1, s2) {
+            let elt = match merged {
+                Merged::Left(a_next) => {
+                    lefts.push(a_next);
+                    a_next
+                }
+                Merged::Both(a_next, b_next) => {
+                    super::prop_assert!(std::ptr::eq(a_next, s1.get(a_next).unwrap()));
+                    super::prop_assert!(std::ptr::eq(b_next, s2.get(b_next).unwrap()));
+                    boths.push(a_next);
+                    a_next
+                }
+                Merged::Right(b_next) => {
+                    rights.push(b_next);
+                    b_next
+                }
+            };
+            super::prop_assert!(i32::from(*elt) > previous);
+            previous = i32::from(*elt);
+        }
+        super::prop_assert_eq!(lefts, set_peeking::difference(s1, s2).collect::<Vec<_>>());
+        super::prop_assert_eq!(boths, set_peeking::intersection(s1, s2).collect::<Vec<_>>());
+        super::prop_assert_eq!(rights, set_peeking::difference(s2, s1).collect::<Vec<_>>());
+
+        let mut it = set_peeking::classify_changes(s1, s2);
+        super::prop_assert_eq!(
+            it.by_ref().collect::<Vec<_>>(),
+            set_peeking::classify(s1, s2)
+                .filter(|merged| !matches!(merged, Merged::Both(..)))
+                .collect::<Vec<_>>()
+        );
+        super::prop_assert!(it.next().is_none());
+        Ok(())
+    }
+
+    fixture_tests! {assert_classify}
+}
+
+mod test_in {
+    use rust_bench_btreeset::set_peeking;
+    use std::collections::BTreeSet;
+    use std::ops::RangeBounds;
+
+    fn assert_in<R>(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+        range: R,
+    ) -> Result<(), super::TestCaseError>
+    where
+        R: RangeBounds<u8> + Clone,
+    {
+        let r1: BTreeSet<u8> = s1.range(range.clone()).cloned().collect();
+        let r2: BTreeSet<u8> = s2.range(range.clone()).cloned().collect();
+        super::prop_assert_eq!(
+            set_peeking::difference_in(s1, s2, range.clone()).collect::<Vec<_>>(),
+            r1.difference(&r2).collect::<Vec<_>>()
+        );
+        super::prop_assert_eq!(
+            set_peeking::intersection_in(s1, s2, range.clone()).collect::<Vec<_>>(),
+            r1.intersection(&r2).collect::<Vec<_>>()
+        );
+        super::prop_assert_eq!(
+            set_peeking::union_in(s1, s2, range.clone()).collect::<Vec<_>>(),
+            r1.union(&r2).collect::<Vec<_>>()
+        );
+        super::prop_assert_eq!(
+            set_peeking::is_disjoint_in(s1, s2, range),
+            r1.is_disjoint(&r2)
+        );
+        Ok(())
+    }
+
+    fn assert_all_in(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+        lo: u8,
+        hi: u8,
+    ) -> Result<(), super::TestCaseError> {
+        let (lo, hi) = if lo <= hi { (lo, hi) } else { (hi, lo) };
+        assert_in(s1, s2, lo..hi)?;
+        assert_in(s1, s2, lo..=hi)?;
+        assert_in(s1, s2, ..hi)?;
+        assert_in(s1, s2, lo..)?;
+        assert_in(s1, s2, ..)
+    }
+
+    fixture_tests! {assert_all_in, lo in super::any::<u8>(), hi in super::any::<u8>()}
+}
+
+mod test_views {
+    use rust_bench_btreeset::set_peeking;
+    use rust_bench_btreeset::sorted_view::SortedView;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[test]
+    fn seek_never_rewinds() {
+        let s: BTreeSet<u8> = (0..10).collect();
+        let set = &s;
+        let mut iter = SortedView::iter(&set);
+        assert!(set.seek(&mut iter, &5));
+        assert_eq!(iter.next(), Some(&5));
+        assert!(set.seek(&mut iter, &2));
+        assert_eq!(iter.next(), Some(&6));
+        assert_eq!(iter.next_back(), Some(&9));
+        assert!(set.seek(&mut iter, &8));
+        assert_eq!(iter.collect::<Vec<_>>(), [&8]);
+
+        let m: BTreeMap<u8, ()> = s.iter().map(|&k| (k, ())).collect();
+        let map = &m;
+        let mut iter = SortedView::iter(&map);
+        assert!(map.seek(&mut iter, &5));
+        assert_eq!(iter.next(), Some(&5));
+        assert!(map.seek(&mut iter, &2));
+        assert_eq!(iter.next(), Some(&6));
+        assert!(map.seek(&mut iter, &20));
+        assert_eq!(iter.next(), None);
+
+        let v: Vec<u8> = s.iter().cloned().collect();
+        let slice = v.as_slice();
+        let mut iter = SortedView::iter(&slice);
+        assert!(slice.seek(&mut iter, &5));
+        assert_eq!(iter.next(), Some(&5));
+        assert!(slice.seek(&mut iter, &2));
+        assert_eq!(iter.next(), Some(&6));
+    }
+
+    #[test]
+    fn can_search() {
+        let s: BTreeSet<u8> = (0..10).collect();
+        let m: BTreeMap<u8, ()> = s.iter().map(|&k| (k, ())).collect();
+        let v: Vec<u8> = s.iter().cloned().collect();
+        assert!((&s).can_search());
+        assert!((&m).can_search());
+        assert!(v.as_slice().can_search());
+        assert!(!s.range(..).can_search());
+    }
+
+    fn assert_views(s1: &BTreeSet<u8>, s2: &BTreeSet<u8>) -> Result<(), super::TestCaseError> {
+        let difference: Vec<&u8> = s1.difference(s2).collect();
+        let intersection: Vec<&u8> = s1.intersection(s2).collect();
+        let union: Vec<&u8> = s1.union(s2).collect();
+
+        // A BTreeSet against a sorted Vec, as from a database query.
+        let v2: Vec<u8> = s2.iter().cloned().collect();
+        let v2 = v2.as_slice();
+        super::prop_assert_eq!(
+            &set_peeking::difference(s1, v2).collect::<Vec<_>>(),
+            &difference
+        );
+        super::prop_assert_eq!(
+            &set_peeking::intersection(s1, v2).collect::<Vec<_>>(),
+            &intersection
+        );
+        super::prop_assert_eq!(&set_peeking::union(s1, v2).collect::<Vec<_>>(), &union);
+
+        // A sorted slice against the keys of a BTreeMap.
+        let v1: Vec<u8> = s1.iter().cloned().collect();
+        let v1 = v1.as_slice();
+        let m2: BTreeMap<u8, ()> = s2.iter().map(|&k| (k, ())).collect();
+        super::prop_assert_eq!(
+            &set_peeking::difference(v1, &m2).collect::<Vec<_>>(),
+            &difference
+        );
+        super::prop_assert_eq!(
+            &set_peeking::intersection(v1, &m2).collect::<Vec<_>>(),
+            &intersection
+        );
+        super::prop_assert_eq!(&set_peeking::union(v1, &m2).collect::<Vec<_>>(), &union);
+
+        // Ranges, which know neither their length nor how to search.
+        super::prop_assert_eq!(
+            &set_peeking::difference(s1.range(..), s2.range(..)).collect::<Vec<_>>(),
+            &difference
+        );
+        super::prop_assert_eq!(
+            &set_peeking::intersection(s1.range(..), s2).collect::<Vec<_>>(),
+            &intersection
+        );
+        super::prop_assert_eq!(
+            &set_peeking::union(s1, s2.range(..)).collect::<Vec<_>>(),
+            &union
+        );
+        Ok(())
+    }
+
+    fixture_tests! {assert_views}
+}
+
+mod test_advance {
+    use rust_bench_btreeset::{set_peeking, set_swivel};
+    use std::collections::BTreeSet;
+
+    // Interleaves next and advance_to (for Some steps), comparing with the
+    // expected elements and checking size_hint and fusedness along the way.
+    fn assert_advance<'a, I, F>(
+        mut it: I,
+        advance_to: F,
+        expected: Vec<&'a u8>,
+        steps: &[Option<u8>],
+    ) -> Result<(), super::TestCaseError>
+    where
+        I: Iterator<Item = &'a u8>,
+        F: Fn(&mut I, &u8),
+    {
+        let mut pos = 0;
+        for step in steps {
+            let (min_len, max_len) = it.size_hint();
+            super::prop_assert!(min_len <= expected.len() - pos);
+            super::prop_assert!(max_len.is_none_or(|max_len| max_len >= expected.len() - pos));
+            match step {
+                None => {
+                    super::prop_assert_eq!(it.next(), expected.get(pos).copied());
+                    pos = (pos + 1).min(expected.len());
+                }
+                Some(bound) => {
+                    advance_to(&mut it, bound);
+                    pos = pos.max(expected.partition_point(|elt| *elt < bound));
+                }
+            }
+        }
+        super::prop_assert_eq!(it.collect::<Vec<_>>(), &expected[pos..]);
+        Ok(())
+    }
+
+    fn assert_all_advance(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+        steps: Vec<Option<u8>>,
+    ) -> Result<(), super::TestCaseError> {
+        let steps = &steps;
+        let difference: Vec<&u8> = s1.difference(s2).collect();
+        let intersection: Vec<&u8> = s1.intersection(s2).collect();
+        let union: Vec<&u8> = s1.union(s2).collect();
+
+        assert_advance(
+            set_peeking::difference(s1, s2),
+            set_peeking::Difference::advance_to,
+            difference.clone(),
+            steps,
+        )?;
+        assert_advance(
+            set_peeking::difference_splice(s1, s2),
+            set_peeking::Difference::advance_to,
+            difference.clone(),
+            steps,
+        )?;
+        assert_advance(
+            set_peeking::intersection(s1, s2),
+            set_peeking::Intersection::advance_to,
+            intersection.clone(),
+            steps,
+        )?;
+        assert_advance(
+            set_peeking::intersection_stitch(s1, s2),
+            set_peeking::Intersection::advance_to,
+            intersection.clone(),
+            steps,
+        )?;
+        assert_advance(
+            set_peeking::union(s1, s2),
+            set_peeking::Union::advance_to,
+            union.clone(),
+            steps,
+        )?;
+        assert_advance(
+            set_peeking::union_splice(s1, s2),
+            set_peeking::Union::advance_to,
+            union.clone(),
+            steps,
+        )?;
+        assert_advance(
+            set_swivel::difference(s1, s2),
+            set_swivel::Difference::advance_to,
+            difference,
+            steps,
+        )?;
+        assert_advance(
+            set_swivel::intersection(s1, s2),
+            set_swivel::Intersection::advance_to,
+            intersection,
+            steps,
+        )?;
+        assert_advance(
+            set_swivel::union(s1, s2),
+            set_swivel::Union::advance_to,
+            union,
+            steps,
+        )
+    }
+
+    #[test]
+    fn past_the_end() {
+        let s1: BTreeSet<u8> = (1..=10).collect();
+        let s2: BTreeSet<u8> = (5..=15).collect();
+        let mut difference = set_peeking::difference(&s1, &s2);
+        difference.advance_to(&200);
+        assert_eq!(difference.size_hint().0, 0);
+        assert_eq!(difference.next(), None);
+        let mut intersection = set_peeking::intersection(&s1, &s2);
+        intersection.advance_to(&200);
+        assert_eq!(intersection.size_hint().0, 0);
+        assert_eq!(intersection.next(), None);
+        let mut union = set_peeking::union(&s1, &s2);
+        union.advance_to(&200);
+        assert_eq!(union.size_hint().0, 0);
+        assert_eq!(union.next(), None);
+        // Advancing back doesn't revive what was skipped.
+        union.advance_to(&0);
+        assert_eq!(union.next(), None);
+        let mut intersection = set_swivel::intersection(&s1, &s2);
+        intersection.advance_to(&200);
+        intersection.advance_to(&0);
+        assert_eq!(intersection.next(), None);
+    }
+
+    fixture_tests! {assert_all_advance, steps in super::any::<Vec<Option<u8>>>()}
+}
+
+mod test_after {
+    use rust_bench_btreeset::set_peeking;
+    use std::collections::BTreeSet;
+
+    fn assert_after(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+        last_seen: u8,
+    ) -> Result<(), super::TestCaseError> {
+        let after = |s: &BTreeSet<u8>| -> BTreeSet<u8> {
+            s.iter().cloned().filter(|&x| x > last_seen).collect()
+        };
+        let (r1, r2) = (after(s1), after(s2));
+        super::prop_assert_eq!(
+            set_peeking::difference_after(s1, s2, &last_seen).collect::<Vec<_>>(),
+            r1.difference(&r2).collect::<Vec<_>>()
+        );
+        super::prop_assert_eq!(
+            set_peeking::intersection_after(s1, s2, &last_seen).collect::<Vec<_>>(),
+            r1.intersection(&r2).collect::<Vec<_>>()
+        );
+        super::prop_assert_eq!(
+            set_peeking::symmdiff_after(s1, s2, &last_seen).collect::<Vec<_>>(),
+            r1.symmetric_difference(&r2).collect::<Vec<_>>()
+        );
+        super::prop_assert_eq!(
+            set_peeking::union_after(s1, s2, &last_seen).collect::<Vec<_>>(),
+            r1.union(&r2).collect::<Vec<_>>()
+        );
+        super::prop_assert_eq!(
+            set_peeking::is_subset_after(s1, s2, &last_seen),
+            r1.is_subset(&r2)
+        );
+        Ok(())
+    }
+
+    // Pages through an operation from the start, resuming each page from
+    // the cursor the previous page returned.
+    fn assert_pages<'a, I, F>(
+        first: I,
+        resume: F,
+        limit: usize,
+        expected: Vec<&u8>,
+    ) -> Result<(), super::TestCaseError>
+    where
+        I: Iterator<Item = &'a u8>,
+        F: Fn(&u8) -> I,
+    {
+        let (mut items, mut cursor) = set_peeking::page(first, limit);
+        super::prop_assert!(items.len() <= limit);
+        while let Some(last_seen) = cursor {
+            super::prop_assert_eq!(items.len() % limit, 0);
+            let (page, next) = set_peeking::page(resume(last_seen), limit);
+            super::prop_assert!(!page.is_empty() && page.len() <= limit);
+            items.extend(page);
+            cursor = next;
+        }
+        super::prop_assert_eq!(items, expected);
+        Ok(())
+    }
+
+    fn assert_all_pages(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+        limit: usize,
+    ) -> Result<(), super::TestCaseError> {
+        assert_pages(
+            set_peeking::difference(s1, s2),
+            |last| set_peeking::difference_after(s1, s2, last),
+            limit,
+            s1.difference(s2).collect(),
+        )?;
+        assert_pages(
+            set_peeking::intersection(s1, s2),
+            |last| set_peeking::intersection_after(s1, s2, last),
+            limit,
+            s1.intersection(s2).collect(),
+        )?;
+        assert_pages(
+            set_peeking::symmdiff(s1, s2),
+            |last| set_peeking::symmdiff_after(s1, s2, last),
+            limit,
+            s1.symmetric_difference(s2).collect(),
+        )?;
+        assert_pages(
+            set_peeking::union(s1, s2),
+            |last| set_peeking::union_after(s1, s2, last),
+            limit,
+            s1.union(s2).collect(),
+        )
+    }
+
+    fn assert_after_and_pages(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+        last_seen: u8,
+        limit: usize,
+    ) -> Result<(), super::TestCaseError> {
+        assert_after(s1, s2, last_seen)?;
+        assert_all_pages(s1, s2, limit)
+    }
+
+    #[test]
+    fn page_ending_on_element_of_both() {
+        let s1: BTreeSet<u8> = [1, 2, 3, 4].iter().cloned().collect();
+        let s2: BTreeSet<u8> = [2, 3, 4, 5].iter().cloned().collect();
+        let (items, cursor) = set_peeking::page(set_peeking::union(&s1, &s2), 2);
+        assert_eq!(items, [&1, &2]);
+        assert_eq!(cursor, Some(&2));
+        let (items, cursor) = set_peeking::page(set_peeking::union_after(&s1, &s2, &2), 2);
+        assert_eq!(items, [&3, &4]);
+        assert_eq!(cursor, Some(&4));
+        let (items, cursor) = set_peeking::page(set_peeking::union_after(&s1, &s2, &4), 2);
+        assert_eq!(items, [&5]);
+        assert_eq!(cursor, None);
+
+        let (items, cursor) = set_peeking::page(set_peeking::intersection(&s1, &s2), 1);
+        assert_eq!(items, [&2]);
+        assert_eq!(cursor, Some(&2));
+        let (items, cursor) = set_peeking::page(set_peeking::intersection_after(&s1, &s2, &2), 2);
+        assert_eq!(items, [&3, &4]);
+        assert_eq!(cursor, None);
+        let (items, cursor) = set_peeking::page(set_peeking::symmdiff_after(&s1, &s2, &2), 2);
+        assert_eq!(items, [&5]);
+        assert_eq!(cursor, None);
+    }
+
+    #[test]
+    fn page_zero() {
+        let empty = BTreeSet::<u8>::new();
+        let (items, cursor) = set_peeking::page(empty.iter(), 0);
+        assert!(items.is_empty());
+        assert_eq!(cursor, None);
+        let s: BTreeSet<u8> = (1..4).collect();
+        let (items, cursor) = set_peeking::page(s.iter(), 0);
+        assert!(items.is_empty());
+        assert_eq!(cursor, Some(&1));
+    }
+
+    fixture_tests! {assert_after_and_pages, last_seen in super::any::<u8>(), limit in 1..20usize}
+}
+
+mod test_fold {
+    use rust_bench_btreeset::{set_new, set_peeking, set_slice};
+    use std::collections::BTreeSet;
+
+    // Folds each strategy explicitly, since the choice
+    // between them depends on the sizes of the sets.
+    fn assert_strategies(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+        skip: usize,
+    ) -> Result<(), super::TestCaseError> {
+        let (small, large) = if s1.len() <= s2.len() {
+            (s1, s2)
+        } else {
+            (s2, s1)
+        };
+        let difference = || s1.difference(s2).collect::<Vec<_>>();
+        let intersection = || small.intersection(large).collect::<Vec<_>>();
+        let symmdiff = || small.symmetric_difference(large).collect::<Vec<_>>();
+        let union = || small.union(large).collect::<Vec<_>>();
+        super::assert_fold(set_new::difference_splice(s1, s2), skip, difference())?;
+        super::assert_fold(
+            set_new::intersection_search(small, large),
+            skip,
+            intersection(),
+        )?;
+        super::assert_fold(
+            set_new::intersection_stitch(small, large),
+            skip,
+            intersection(),
+        )?;
+        super::assert_fold(set_new::symmdiff_splice(small, large), skip, symmdiff())?;
+        super::assert_fold(set_new::union_splice(small, large), skip, union())?;
+        super::assert_fold(set_peeking::difference_splice(s1, s2), skip, difference())?;
+        super::assert_fold(
+            set_peeking::intersection_search(small, large),
+            skip,
+            intersection(),
+        )?;
+        super::assert_fold(
+            set_peeking::intersection_stitch(small, large),
+            skip,
+            intersection(),
+        )?;
+        super::assert_fold(set_peeking::symmdiff_splice(small, large), skip, symmdiff())?;
+        super::assert_fold(set_peeking::union_splice(small, large), skip, union())?;
+        super::assert_fold(
+            set_peeking::intersection_pairs(s1, s2).map(|(a, _)| a),
+            skip,
+            s1.intersection(s2).collect(),
+        )?;
+        let (small, large) = (super::to_vec(small), super::to_vec(large));
+        super::assert_fold(
+            set_slice::intersection_gallop(&small, &large),
+            skip,
+            intersection(),
+        )?;
+        super::assert_fold(
+            set_slice::intersection_search(&small, &large),
+            skip,
+            intersection(),
+        )?;
+        super::assert_fold(
+            set_slice::intersection_stitch(&small, &large),
+            skip,
+            intersection(),
+        )
+    }
+
+    fixture_tests! {assert_strategies, skip in 0..3usize}
+}
+
+mod test_ends {
+    use rust_bench_btreeset::set_peeking;
+    use std::collections::BTreeSet;
+
+    fn assert_strategies(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+        skip: usize,
+    ) -> Result<(), super::TestCaseError> {
+        let (small, large) = if s1.len() <= s2.len() {
+            (s1, s2)
+        } else {
+            (s2, s1)
+        };
+        let difference = || s1.difference(s2).collect::<Vec<_>>();
+        let intersection = || small.intersection(large).collect::<Vec<_>>();
+        let symmdiff = || s1.symmetric_difference(s2).collect::<Vec<_>>();
+        let union = || s1.union(s2).collect::<Vec<_>>();
+        super::assert_ends(set_peeking::difference(s1, s2), skip, difference())?;
+        super::assert_ends(set_peeking::difference_splice(s1, s2), skip, difference())?;
+        super::assert_ends(
+            set_peeking::intersection(s1, s2),
+            skip,
+            s1.intersection(s2).collect(),
+        )?;
+        super::assert_ends(
+            set_peeking::intersection_search(small, large),
+            skip,
+            intersection(),
+        )?;
+        super::assert_ends(
+            set_peeking::intersection_stitch(small, large),
+            skip,
+            intersection(),
+        )?;
+        super::assert_ends(set_peeking::symmdiff(s1, s2), skip, symmdiff())?;
+        super::assert_ends(set_peeking::symmdiff_splice(small, large), skip, symmdiff())?;
+        super::assert_ends(set_peeking::union(s1, s2), skip, union())?;
+        super::assert_ends(set_peeking::union_splice(small, large), skip, union())?;
+        let mut pairs = set_peeking::intersection_pairs(s1, s2);
+        pairs.by_ref().take(skip).for_each(drop);
+        let expected = s1.intersection(s2).skip(skip).last();
+        super::prop_assert_eq!(pairs.last(), expected.map(|last| (last, last)));
+        Ok(())
+    }
+
+    fixture_tests! {assert_strategies, skip in 0..3usize}
+}
+
+mod test_size_hint {
+    use rust_bench_btreeset::{set_new, set_peeking};
+    use std::collections::BTreeSet;
+
+    // Checks the strategies the size-based choices may not pick,
+    // and the constructors of set_peeking that start from what's known
+    // about a range, or that skip ahead.
+    fn assert_strategies(
+        s1: &BTreeSet<u8>,
+        s2: &BTreeSet<u8>,
+        bound: u8,
+    ) -> Result<(), super::TestCaseError> {
+        let (small, large) = if s1.len() <= s2.len() {
+            (s1, s2)
+        } else {
+            (s2, s1)
+        };
+        let difference = s1.difference(s2).count();
+        let intersection = s1.intersection(s2).count();
+        let symmdiff = s1.symmetric_difference(s2).count();
+        let union = s1.union(s2).count();
+        super::assert_size_hints(set_new::difference_splice(s1, s2), difference)?;
+        super::assert_size_hints(set_new::intersection_search(small, large), intersection)?;
+        super::assert_size_hints(set_new::intersection_stitch(small, large), intersection)?;
+        super::assert_size_hints(set_new::symmdiff_splice(small, large), symmdiff)?;
+        super::assert_size_hints(set_new::union_splice(small, large), union)?;
+        super::assert_size_hints(set_peeking::difference_splice(s1, s2), difference)?;
+        super::assert_size_hints(set_peeking::intersection_search(small, large), intersection)?;
+        super::assert_size_hints(set_peeking::intersection_stitch(small, large), intersection)?;
+        super::assert_size_hints(set_peeking::intersection_pairs(s1, s2), intersection)?;
+        super::assert_size_hints(set_peeking::symmdiff_splice(small, large), symmdiff)?;
+        super::assert_size_hints(set_peeking::union_splice(small, large), union)?;
+        super::assert_size_hints(set_peeking::classify(s1, s2), union)?;
+        super::assert_size_hints(set_peeking::classify_changes(s1, s2), symmdiff)?;
+
+        let above =
+            |elts: &BTreeSet<u8>| -> BTreeSet<u8> { elts.range(bound..).copied().collect() };
+        let (a1, a2) = (above(s1), above(s2));
+        super::assert_size_hints(
+            set_peeking::difference_in(s1, s2, bound..),
+            a1.difference(&a2).count(),
+        )?;
+        super::assert_size_hints(
+            set_peeking::intersection_in(s1, s2, bound..),
+            a1.intersection(&a2).count(),
+        )?;
+        super::assert_size_hints(
+            set_peeking::union_in(s1, s2, bound..),
+            a1.union(&a2).count(),
+        )?;
+        super::assert_size_hints(
+            set_peeking::symmdiff_after(s1, s2, &bound),
+            s1.symmetric_difference(s2)
+                .filter(|elt| **elt > bound)
+                .count(),
+        )?;
+
+        let mut difference = set_peeking::difference(s1, s2);
+        difference.advance_to(&bound);
+        super::assert_size_hints(difference, a1.difference(&a2).count())?;
+        let mut intersection = set_peeking::intersection(s1, s2);
+        intersection.advance_to(&bound);
+        super::assert_size_hints(intersection, a1.intersection(&a2).count())?;
+        let mut union = set_peeking::union(s1, s2);
+        union.advance_to(&bound);
+        super::assert_size_hints(union, a1.union(&a2).count())
+    }
+
+    #[test]
+    fn tight_for_whole_sets() {
+        let s1: BTreeSet<u8> = (1..=10).collect();
+        let s2: BTreeSet<u8> = (9..=12).collect();
+        assert_eq!(set_peeking::difference(&s1, &s2).size_hint(), (6, Some(10)));
+        assert_eq!(set_peeking::union(&s1, &s2).size_hint(), (10, Some(14)));
+        assert_eq!(set_peeking::symmdiff(&s1, &s2).size_hint(), (6, Some(14)));
+        let mut union = set_peeking::union(&s1, &s2);
+        union.by_ref().take(10).for_each(drop);
+        assert_eq!(union.size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn shrinking_as_either_side_is_consumed() {
+        let s1: BTreeSet<u8> = (0..100).collect();
+        let s2: BTreeSet<u8> = (0..90).collect();
+        let mut difference = set_new::difference(&s1, &s2);
+        assert_eq!(difference.size_hint(), (10, Some(100)));
+        assert_eq!(difference.next(), Some(&90));
+        assert_eq!(difference.size_hint(), (9, Some(9)));
+        let mut difference = set_peeking::difference(&s1, &s2);
+        assert_eq!(difference.size_hint(), (10, Some(100)));
+        assert_eq!(difference.next(), Some(&90));
+        assert_eq!(difference.size_hint(), (9, Some(9)));
+
+        let s1: BTreeSet<u8> = (0..100).step_by(2).collect();
+        let s2: BTreeSet<u8> = (0..10).chain(90..100).collect();
+        let mut intersection = set_new::intersection(&s1, &s2);
+        assert_eq!(intersection.size_hint(), (0, Some(20)));
+        intersection.by_ref().take(5).for_each(drop);
+        assert_eq!(intersection.size_hint(), (0, Some(11)));
+        let mut intersection = set_peeking::intersection(&s1, &s2);
+        assert_eq!(intersection.size_hint(), (0, Some(20)));
+        intersection.by_ref().take(5).for_each(drop);
+        assert_eq!(intersection.size_hint(), (0, Some(11)));
+    }
+
+    fixture_tests! {assert_strategies, bound in super::any::<u8>()}
+}